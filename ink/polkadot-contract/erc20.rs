@@ -1,12 +1,157 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+use ink::prelude::{string::String, vec::Vec};
+use ink::primitives::AccountId;
+
+/// PSP22 error type as defined by the standard
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    Custom(String),
+    InsufficientBalance,
+    InsufficientAllowance,
+    ZeroRecipientAddress,
+    ZeroSenderAddress,
+    SafeTransferCheckFailed(String),
+}
+
+/// PSP22 fungible token interface
+/// Selectors are derived from `PSP22::<message>` and match the standard
+#[ink::trait_definition]
+pub trait PSP22 {
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u128;
+
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> u128;
+
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: u128) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn increase_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: u128,
+    ) -> Result<(), PSP22Error>;
+
+    #[ink(message)]
+    fn decrease_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: u128,
+    ) -> Result<(), PSP22Error>;
+}
+
+/// Hook for contracts that react to incoming tokens, called by `transfer_and_call`
+/// Returning an error rejects the tokens and reverts the transfer
+#[ink::trait_definition]
+pub trait PSP22Receiver {
+    #[ink(message)]
+    fn on_token_received(
+        &mut self,
+        from: AccountId,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+}
+
+/// PSP22 metadata extension
+#[ink::trait_definition]
+pub trait PSP22Metadata {
+    #[ink(message)]
+    fn token_name(&self) -> Option<String>;
+
+    #[ink(message)]
+    fn token_symbol(&self) -> Option<String>;
+
+    #[ink(message)]
+    fn token_decimals(&self) -> u8;
+}
+
 /// Ink! ERC-20 Token Implementation for Polkadot/Substrate
 /// Standard fungible token with PSP22 interface
 
 #[ink::contract]
 mod erc20 {
+    use super::{PSP22Error, PSP22Metadata, PSP22};
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::hash::Blake2x256;
+    use ink::prelude::{format, string::String, vec::Vec};
     use ink::storage::Mapping;
 
+    /// Longest memo accepted by `transfer_with_memo`, in bytes
+    const MAX_MEMO_LEN: usize = 256;
+
+    /// Highest transfer fee the owner may configure, in basis points (10%)
+    const MAX_FEE_BPS: u16 = 1_000;
+
+    /// Highest `decimals` accepted by the constructors
+    const MAX_DECIMALS: u8 = 36;
+
+    /// Linear vesting schedule minted to a beneficiary as it unlocks
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Vesting {
+        /// Total amount released over the schedule
+        pub total: Balance,
+        /// Block timestamp (ms) at which unlocking begins
+        pub start_ts: u64,
+        /// Time (ms) from `start_ts` until everything is unlocked
+        pub duration: u64,
+        /// Amount already claimed
+        pub claimed: Balance,
+    }
+
+    impl Vesting {
+        /// Amount unlocked at `now`, never more than `total`
+        fn vested_at(&self, now: u64) -> Balance {
+            let elapsed = now.saturating_sub(self.start_ts);
+            if elapsed >= self.duration {
+                return self.total;
+            }
+            // Split the product so `total * elapsed` cannot overflow
+            let duration = Balance::from(self.duration);
+            let elapsed = Balance::from(elapsed);
+            (self.total / duration) * elapsed + (self.total % duration) * elapsed / duration
+        }
+    }
+
+    /// Large transfer waiting out the timelock before it can be executed
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PendingTransfer {
+        /// Account the tokens are taken from
+        pub from: AccountId,
+        /// Recipient of the tokens
+        pub to: AccountId,
+        /// Amount to transfer
+        pub value: Balance,
+        /// Block timestamp (ms) from which the transfer may be executed
+        pub unlock_ts: u64,
+    }
+
     /// ERC-20 Token Storage
     #[ink(storage)]
     pub struct Erc20 {
@@ -16,14 +161,60 @@ mod erc20 {
         balances: Mapping<AccountId, Balance>,
         /// Token allowances
         allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// Spenders holding a non-zero allowance from each owner
+        spenders_of: Mapping<AccountId, Vec<AccountId>>,
         /// Token name
         name: String,
         /// Token symbol
         symbol: String,
         /// Token decimals
         decimals: u8,
-        /// Contract owner
-        owner: AccountId,
+        /// Contract owner, `None` once ownership is renounced
+        owner: Option<AccountId>,
+        /// Optional ceiling on total supply
+        max_supply: Option<Balance>,
+        /// Whether transfers, approvals and minting are halted
+        paused: bool,
+        /// Accounts blocked from sending, receiving and approving
+        frozen: Mapping<AccountId, bool>,
+        /// Cumulative amount of tokens burned
+        total_burned: Balance,
+        /// Number of accounts holding a non-zero balance
+        holder_count: u32,
+        /// Whether `approve` refuses to change one non-zero allowance to another
+        safe_approve: bool,
+        /// Optional ceiling on the amount a single `mint` call may create
+        max_mint_per_call: Option<Balance>,
+        /// Id of the most recent snapshot (0 before the first one)
+        current_snapshot_id: u32,
+        /// Total supply recorded by each snapshot
+        snapshot_supply: Mapping<u32, Balance>,
+        /// Balance an account held at a snapshot, written lazily before it changes
+        balance_checkpoints: Mapping<(AccountId, u32), Balance>,
+        /// Snapshot ids each account has a checkpoint for, in ascending order
+        checkpoint_ids: Mapping<AccountId, Vec<u32>>,
+        /// Treasury or burn-holding accounts left out of `circulating_supply`
+        excluded_from_circulation: Mapping<AccountId, bool>,
+        /// Enumerable list of the excluded accounts
+        excluded_accounts: Vec<AccountId>,
+        /// Vesting schedule per beneficiary
+        vestings: Mapping<AccountId, Vesting>,
+        /// Fee taken from each transfer, in basis points (0 disables)
+        fee_bps: u16,
+        /// Account receiving transfer fees
+        treasury: AccountId,
+        /// Optional ceiling on any non-exempt account's balance
+        max_balance: Option<Balance>,
+        /// Transfers of at least this amount must go through the timelock
+        large_transfer_threshold: Option<Balance>,
+        /// Delay (ms) before a requested large transfer may be executed
+        timelock_delay: u64,
+        /// Id assigned to the next requested large transfer
+        next_pending_id: u64,
+        /// Large transfers waiting out the timelock, by id
+        pending_transfers: Mapping<u64, PendingTransfer>,
+        /// Next signature nonce per account, consumed by `permit`
+        nonces: Mapping<AccountId, u64>,
     }
 
     /// Events
@@ -36,13 +227,91 @@ mod erc20 {
         value: Balance,
     }
 
+    #[ink(event)]
+    pub struct TransferMemo {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        memo: Vec<u8>,
+    }
+
+    #[ink(event)]
+    pub struct LargeTransferRequested {
+        id: u64,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        unlock_ts: u64,
+    }
+
+    #[ink(event)]
+    pub struct ForcedTransfer {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        #[ink(topic)]
+        by: AccountId,
+    }
+
     #[ink(event)]
     pub struct Approval {
         #[ink(topic)]
         owner: AccountId,
         #[ink(topic)]
         spender: AccountId,
-        value: Balance,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        previous: AccountId,
+        #[ink(topic)]
+        new: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct VestingCreated {
+        #[ink(topic)]
+        beneficiary: AccountId,
+        total: Balance,
+        start_ts: u64,
+        duration: u64,
+    }
+
+    #[ink(event)]
+    pub struct Snapshot {
+        id: u32,
+    }
+
+    #[ink(event)]
+    pub struct AccountFrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AccountUnfrozen {
+        #[ink(topic)]
+        account: AccountId,
     }
 
     /// Errors
@@ -53,12 +322,46 @@ mod erc20 {
         InsufficientAllowance,
         Unauthorized,
         ZeroAddress,
+        Overflow,
+        CapExceeded,
+        Paused,
+        AccountFrozen,
+        UnsafeApproval,
+        MemoTooLong,
+        MintLimitExceeded,
+        InvalidSnapshot,
+        ReceiverRejected,
+        VestingExists,
+        NoVesting,
+        VestingNotStarted,
+        FeeTooHigh,
+        MaxBalanceExceeded,
+        TimelockRequired,
+        BelowLargeThreshold,
+        NoPendingTransfer,
+        TimelockNotElapsed,
+        SupplyUnderflow,
+        PermitExpired,
+        InvalidSignature,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
 
+    impl From<Error> for PSP22Error {
+        fn from(error: Error) -> Self {
+            match error {
+                Error::InsufficientBalance => PSP22Error::InsufficientBalance,
+                Error::InsufficientAllowance => PSP22Error::InsufficientAllowance,
+                Error::ZeroAddress => PSP22Error::ZeroRecipientAddress,
+                other => PSP22Error::Custom(format!("{:?}", other)),
+            }
+        }
+    }
+
     impl Erc20 {
         /// Constructor
+        /// Every constructor panics on an empty name or symbol, or on
+        /// `decimals` above 36
         #[ink(constructor)]
         pub fn new(
             name: String,
@@ -66,9 +369,26 @@ mod erc20 {
             decimals: u8,
             initial_supply: Balance,
         ) -> Self {
+            Self::new_capped(name, symbol, decimals, initial_supply, None)
+        }
+
+        /// Constructor with an optional cap on total supply
+        #[ink(constructor)]
+        pub fn new_capped(
+            name: String,
+            symbol: String,
+            decimals: u8,
+            initial_supply: Balance,
+            max_supply: Option<Balance>,
+        ) -> Self {
+            if let Some(cap) = max_supply {
+                assert!(initial_supply <= cap, "initial supply exceeds max supply");
+            }
+
             let caller = Self::env().caller();
-            let mut balances = Mapping::default();
-            balances.insert(caller, &initial_supply);
+            let mut token = Self::empty(name, symbol, decimals, max_supply);
+            token.set_balance(&caller, initial_supply);
+            token.total_supply = initial_supply;
 
             Self::env().emit_event(Transfer {
                 from: None,
@@ -76,17 +396,100 @@ mod erc20 {
                 value: initial_supply,
             });
 
+            token
+        }
+
+        /// Constructor seeding several holders at genesis instead of the deployer
+        /// Total supply is the sum of the balances; duplicate holders are rejected
+        #[ink(constructor)]
+        pub fn new_with_distribution(
+            name: String,
+            symbol: String,
+            decimals: u8,
+            holders: Vec<(AccountId, Balance)>,
+        ) -> Self {
+            let mut accounts: Vec<AccountId> = holders.iter().map(|(holder, _)| *holder).collect();
+            accounts.sort_unstable();
+            assert!(
+                accounts.windows(2).all(|pair| pair[0] != pair[1]),
+                "duplicate holder"
+            );
+
+            let mut token = Self::empty(name, symbol, decimals, None);
+            for (holder, value) in holders {
+                assert!(holder != AccountId::from([0u8; 32]), "zero address holder");
+                token.total_supply = token
+                    .total_supply
+                    .checked_add(value)
+                    .expect("total supply overflow");
+                token.set_balance(&holder, value);
+
+                Self::env().emit_event(Transfer {
+                    from: None,
+                    to: Some(holder),
+                    value,
+                });
+            }
+
+            token
+        }
+
+        /// Fresh token state with no balances, owned by the caller
+        fn empty(name: String, symbol: String, decimals: u8, max_supply: Option<Balance>) -> Self {
+            assert!(!name.is_empty(), "empty name");
+            assert!(!symbol.is_empty(), "empty symbol");
+            assert!(decimals <= MAX_DECIMALS, "invalid decimals");
+
+            let caller = Self::env().caller();
             Self {
-                total_supply: initial_supply,
-                balances,
+                total_supply: 0,
+                balances: Mapping::default(),
                 allowances: Mapping::default(),
+                spenders_of: Mapping::default(),
                 name,
                 symbol,
                 decimals,
-                owner: caller,
+                owner: Some(caller),
+                max_supply,
+                paused: false,
+                frozen: Mapping::default(),
+                total_burned: 0,
+                holder_count: 0,
+                safe_approve: false,
+                max_mint_per_call: None,
+                current_snapshot_id: 0,
+                snapshot_supply: Mapping::default(),
+                balance_checkpoints: Mapping::default(),
+                checkpoint_ids: Mapping::default(),
+                excluded_from_circulation: Mapping::default(),
+                excluded_accounts: Vec::new(),
+                vestings: Mapping::default(),
+                fee_bps: 0,
+                treasury: caller,
+                max_balance: None,
+                large_transfer_threshold: None,
+                timelock_delay: 0,
+                next_pending_id: 0,
+                pending_transfers: Mapping::default(),
+                nonces: Mapping::default(),
             }
         }
 
+        /// Constructor that can require allowances be zeroed before being changed,
+        /// closing the approve front-running race
+        #[ink(constructor)]
+        pub fn new_with_safe_approve(
+            name: String,
+            symbol: String,
+            decimals: u8,
+            initial_supply: Balance,
+            safe_approve: bool,
+        ) -> Self {
+            let mut token = Self::new_capped(name, symbol, decimals, initial_supply, None);
+            token.safe_approve = safe_approve;
+            token
+        }
+
         /// Returns token name
         #[ink(message)]
         pub fn name(&self) -> String {
@@ -111,148 +514,2338 @@ mod erc20 {
             self.total_supply
         }
 
+        /// Returns the cumulative amount of tokens burned
+        #[ink(message)]
+        pub fn get_total_burned(&self) -> Balance {
+            self.total_burned
+        }
+
+        /// Returns the number of accounts with a non-zero balance
+        #[ink(message)]
+        pub fn get_holder_count(&self) -> u32 {
+            self.holder_count
+        }
+
+        /// Returns the total supply minus balances of excluded accounts
+        #[ink(message)]
+        pub fn circulating_supply(&self) -> Balance {
+            self.excluded_accounts
+                .iter()
+                .fold(self.total_supply, |supply, account| {
+                    supply.saturating_sub(self.balance_of(*account))
+                })
+        }
+
+        /// Returns whether an account is left out of the circulating supply
+        #[ink(message)]
+        pub fn is_excluded_from_circulation(&self, account: AccountId) -> bool {
+            self.excluded_from_circulation.get(account).unwrap_or(false)
+        }
+
+        /// Leave an account's balance out of the circulating supply (owner only)
+        #[ink(message)]
+        pub fn exclude_from_circulation(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            if !self.is_excluded_from_circulation(account) {
+                self.excluded_from_circulation.insert(account, &true);
+                self.excluded_accounts.push(account);
+            }
+            Ok(())
+        }
+
+        /// Count an excluded account towards the circulating supply again (owner only)
+        #[ink(message)]
+        pub fn include_in_circulation(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.excluded_from_circulation.remove(account);
+            self.excluded_accounts
+                .retain(|excluded| *excluded != account);
+            Ok(())
+        }
+
+        /// Returns the supply cap, if any
+        #[ink(message)]
+        pub fn max_supply(&self) -> Option<Balance> {
+            self.max_supply
+        }
+
+        /// Returns how much more can be minted before hitting the supply cap
+        /// When uncapped, this is the headroom before `total_supply` overflows
+        #[ink(message)]
+        pub fn remaining_mintable(&self) -> Balance {
+            self.max_supply
+                .unwrap_or(Balance::MAX)
+                .saturating_sub(self.total_supply)
+        }
+
+        /// Returns whether non-zero to non-zero approvals are rejected
+        #[ink(message)]
+        pub fn safe_approve(&self) -> bool {
+            self.safe_approve
+        }
+
+        /// Returns the per-call mint limit, if any
+        #[ink(message)]
+        pub fn max_mint_per_call(&self) -> Option<Balance> {
+            self.max_mint_per_call
+        }
+
+        /// Set or clear the per-call mint limit (owner only)
+        /// Bounds the damage a compromised owner key can do in one call;
+        /// independent of the total supply cap
+        #[ink(message)]
+        pub fn set_max_mint_per_call(&mut self, limit: Option<Balance>) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_mint_per_call = limit;
+            Ok(())
+        }
+
+        /// Returns the transfer fee in basis points
+        #[ink(message)]
+        pub fn fee_bps(&self) -> u16 {
+            self.fee_bps
+        }
+
+        /// Returns the account receiving transfer fees
+        #[ink(message)]
+        pub fn treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        /// Set the transfer fee and the treasury it is routed to (owner only)
+        /// The fee is capped at 1000 bps; transfers to or from the treasury are exempt
+        #[ink(message)]
+        pub fn set_transfer_fee(&mut self, fee_bps: u16, treasury: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            Self::ensure_not_zero(&treasury)?;
+            if fee_bps > MAX_FEE_BPS {
+                return Err(Error::FeeTooHigh);
+            }
+            self.fee_bps = fee_bps;
+            self.treasury = treasury;
+            Ok(())
+        }
+
+        /// Returns the per-account balance cap, if any
+        #[ink(message)]
+        pub fn max_balance(&self) -> Option<Balance> {
+            self.max_balance
+        }
+
+        /// Set or clear the per-account balance cap (owner only)
+        /// The owner and the treasury are exempt
+        #[ink(message)]
+        pub fn set_max_balance(&mut self, max_balance: Option<Balance>) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_balance = max_balance;
+            Ok(())
+        }
+
+        /// Returns the amount from which transfers are timelocked, if any
+        #[ink(message)]
+        pub fn large_transfer_threshold(&self) -> Option<Balance> {
+            self.large_transfer_threshold
+        }
+
+        /// Returns the timelock delay for large transfers, in milliseconds
+        #[ink(message)]
+        pub fn timelock_delay(&self) -> u64 {
+            self.timelock_delay
+        }
+
+        /// Configure the large transfer timelock (owner only)
+        /// A `None` threshold disables it
+        #[ink(message)]
+        pub fn set_large_transfer_timelock(
+            &mut self,
+            threshold: Option<Balance>,
+            delay: u64,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            self.large_transfer_threshold = threshold;
+            self.timelock_delay = delay;
+            Ok(())
+        }
+
+        /// Returns the contract owner, if any
+        #[ink(message)]
+        pub fn owner(&self) -> Option<AccountId> {
+            self.owner
+        }
+
         /// Returns balance of an account
         #[ink(message)]
         pub fn balance_of(&self, owner: AccountId) -> Balance {
             self.balances.get(owner).unwrap_or(0)
         }
 
+        /// Returns the nonce the next signed approval from `owner` must carry
+        /// (0 if the account has never used one)
+        #[ink(message)]
+        pub fn nonce_of(&self, owner: AccountId) -> u64 {
+            self.nonces.get(owner).unwrap_or(0)
+        }
+
+        /// Returns the BLAKE2-256 hash `owner` signs to authorise `permit`:
+        /// the SCALE-encoded `(contract, owner, spender, value, nonce, deadline)`
+        /// with the owner's current nonce
+        #[ink(message)]
+        pub fn permit_hash(
+            &self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+            deadline: Timestamp,
+        ) -> [u8; 32] {
+            let contract = self.env().account_id();
+            self.env().hash_encoded::<Blake2x256, _>(&(
+                contract,
+                owner,
+                spender,
+                value,
+                self.nonce_of(owner),
+                deadline,
+            ))
+        }
+
         /// Returns allowance from owner to spender
         #[ink(message)]
         pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
             self.allowances.get((owner, spender)).unwrap_or(0)
         }
 
+        /// Returns the spenders `owner` has a non-zero allowance for
+        #[ink(message)]
+        pub fn get_spenders(&self, owner: AccountId) -> Vec<AccountId> {
+            self.spenders_of.get(owner).unwrap_or_default()
+        }
+
+        /// Returns each spender of `owner` with its current allowance
+        #[ink(message)]
+        pub fn get_allowances(&self, owner: AccountId) -> Vec<(AccountId, Balance)> {
+            self.get_spenders(owner)
+                .into_iter()
+                .map(|spender| (spender, self.allowance(owner, spender)))
+                .collect()
+        }
+
         /// Transfer tokens
         #[ink(message)]
         pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            self.ensure_not_paused()?;
+            self.ensure_not_large_transfer(value)?;
             let from = self.env().caller();
             self.transfer_from_to(&from, &to, value)
         }
 
-        /// Approve spender to spend tokens
+        /// Queue a transfer at or above the large transfer threshold,
+        /// returning the id to execute it with once the delay has passed
         #[ink(message)]
-        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
-            let owner = self.env().caller();
-            self.allowances.insert((owner, spender), &value);
+        pub fn request_large_transfer(&mut self, to: AccountId, value: Balance) -> Result<u64> {
+            self.ensure_not_paused()?;
+            if !self.is_large_transfer(value) {
+                return Err(Error::BelowLargeThreshold);
+            }
+            let from = self.env().caller();
+            self.ensure_can_transfer(&from, &to, value)?;
 
-            self.env().emit_event(Approval {
-                owner,
-                spender,
+            let id = self.next_pending_id;
+            let unlock_ts = self
+                .env()
+                .block_timestamp()
+                .checked_add(self.timelock_delay)
+                .ok_or(Error::Overflow)?;
+            self.pending_transfers.insert(
+                id,
+                &PendingTransfer {
+                    from,
+                    to,
+                    value,
+                    unlock_ts,
+                },
+            );
+            self.next_pending_id = id.checked_add(1).ok_or(Error::Overflow)?;
+
+            self.env().emit_event(LargeTransferRequested {
+                id,
+                from,
+                to,
                 value,
+                unlock_ts,
             });
 
-            Ok(())
+            Ok(id)
         }
 
-        /// Transfer tokens on behalf of another account
+        /// Complete a requested large transfer once its timelock has elapsed
+        /// Only the account the tokens are taken from may execute it
         #[ink(message)]
-        pub fn transfer_from(
-            &mut self,
-            from: AccountId,
-            to: AccountId,
-            value: Balance,
-        ) -> Result<()> {
-            let caller = self.env().caller();
-            let allowance = self.allowance(from, caller);
-
-            if allowance < value {
-                return Err(Error::InsufficientAllowance);
+        pub fn execute_large_transfer(&mut self, id: u64) -> Result<()> {
+            self.ensure_not_paused()?;
+            let pending = self
+                .pending_transfers
+                .get(id)
+                .ok_or(Error::NoPendingTransfer)?;
+            if self.env().caller() != pending.from {
+                return Err(Error::Unauthorized);
+            }
+            if self.env().block_timestamp() < pending.unlock_ts {
+                return Err(Error::TimelockNotElapsed);
             }
 
-            self.allowances.insert((from, caller), &(allowance - value));
-            self.transfer_from_to(&from, &to, value)
+            self.pending_transfers.remove(id);
+            self.transfer_from_to(&pending.from, &pending.to, pending.value)
         }
 
-        /// Mint new tokens (owner only)
+        /// Drop a requested large transfer before it is executed
         #[ink(message)]
-        pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<()> {
-            let caller = self.env().caller();
-            if caller != self.owner {
+        pub fn cancel_large_transfer(&mut self, id: u64) -> Result<()> {
+            let pending = self
+                .pending_transfers
+                .get(id)
+                .ok_or(Error::NoPendingTransfer)?;
+            if self.env().caller() != pending.from {
                 return Err(Error::Unauthorized);
             }
-
-            let balance = self.balance_of(to);
-            self.balances.insert(to, &(balance + value));
-            self.total_supply += value;
-
-            self.env().emit_event(Transfer {
-                from: None,
-                to: Some(to),
-                value,
-            });
-
+            self.pending_transfers.remove(id);
             Ok(())
         }
 
-        /// Burn tokens
+        /// Returns a pending large transfer, if any
         #[ink(message)]
-        pub fn burn(&mut self, value: Balance) -> Result<()> {
-            let caller = self.env().caller();
-            let balance = self.balance_of(caller);
+        pub fn pending_transfer(&self, id: u64) -> Option<PendingTransfer> {
+            self.pending_transfers.get(id)
+        }
 
-            if balance < value {
-                return Err(Error::InsufficientBalance);
+        /// Transfer tokens with a memo for exchanges and accounting tools
+        /// Emits `TransferMemo` after the regular `Transfer` event
+        #[ink(message)]
+        pub fn transfer_with_memo(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            memo: Vec<u8>,
+        ) -> Result<()> {
+            if memo.len() > MAX_MEMO_LEN {
+                return Err(Error::MemoTooLong);
             }
+            self.transfer(to, value)?;
 
-            self.balances.insert(caller, &(balance - value));
-            self.total_supply -= value;
-
-            self.env().emit_event(Transfer {
-                from: Some(caller),
-                to: None,
+            self.env().emit_event(TransferMemo {
+                from: self.env().caller(),
+                to,
                 value,
+                memo,
             });
 
             Ok(())
         }
 
-        /// Internal transfer helper
-        fn transfer_from_to(
+        /// Transfer tokens, then notify `to` via `PSP22Receiver::on_token_received`
+        /// if it is a contract. A failed or rejecting callback fails the message,
+        /// which reverts the transfer along with it
+        #[ink(message)]
+        pub fn transfer_and_call(
             &mut self,
-            from: &AccountId,
-            to: &AccountId,
+            to: AccountId,
             value: Balance,
+            data: Vec<u8>,
         ) -> Result<()> {
-            let from_balance = self.balance_of(*from);
-            if from_balance < value {
+            self.transfer(to, value)?;
+            if !self.env().is_contract(&to) {
+                return Ok(());
+            }
+
+            let from = self.env().caller();
+            let result = build_call::<ink::env::DefaultEnvironment>()
+                .call(to)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22Receiver::on_token_received"
+                    )))
+                    .push_arg(from)
+                    .push_arg(value)
+                    .push_arg(data),
+                )
+                .returns::<core::result::Result<(), PSP22Error>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(Ok(()))) => Ok(()),
+                _ => Err(Error::ReceiverRejected),
+            }
+        }
+
+        /// Transfer tokens to many recipients in one call
+        /// Any failing transfer returns an error, which reverts the whole batch
+        #[ink(message)]
+        pub fn batch_transfer(&mut self, recipients: Vec<(AccountId, Balance)>) -> Result<()> {
+            self.ensure_not_paused()?;
+            let from = self.env().caller();
+
+            let mut total: Balance = 0;
+            for (_, value) in recipients.iter() {
+                self.ensure_not_large_transfer(*value)?;
+                total = total.checked_add(*value).ok_or(Error::Overflow)?;
+            }
+            if self.balance_of(from) < total {
                 return Err(Error::InsufficientBalance);
             }
 
-            self.balances.insert(*from, &(from_balance - value));
-            let to_balance = self.balance_of(*to);
-            self.balances.insert(*to, &(to_balance + value));
+            for (to, value) in recipients {
+                self.transfer_from_to(&from, &to, value)?;
+            }
+
+            Ok(())
+        }
+
+        /// Approve spender to spend tokens
+        /// With `safe_approve` enabled, a non-zero allowance must be zeroed first
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            self.ensure_not_paused()?;
+            let owner = self.env().caller();
+            self.ensure_not_frozen(&owner)?;
+            if self.safe_approve && value != 0 && self.allowance(owner, spender) != 0 {
+                return Err(Error::UnsafeApproval);
+            }
+            self.set_allowance(owner, spender, value);
+
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount: value,
+            });
+
+            Ok(())
+        }
+
+        /// Atomically increase the allowance granted to spender
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            self.ensure_not_paused()?;
+            let owner = self.env().caller();
+            self.ensure_not_frozen(&owner)?;
+            let allowance = self
+                .allowance(owner, spender)
+                .checked_add(delta)
+                .ok_or(Error::Overflow)?;
+            self.set_allowance(owner, spender, allowance);
+
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount: allowance,
+            });
+
+            Ok(())
+        }
+
+        /// Atomically decrease the allowance granted to spender
+        #[ink(message)]
+        pub fn decrease_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            self.ensure_not_paused()?;
+            let owner = self.env().caller();
+            self.ensure_not_frozen(&owner)?;
+            let allowance = self
+                .allowance(owner, spender)
+                .checked_sub(delta)
+                .ok_or(Error::InsufficientAllowance)?;
+            self.set_allowance(owner, spender, allowance);
+
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount: allowance,
+            });
+
+            Ok(())
+        }
+
+        /// Set `owner`'s allowance for `spender` from an off-chain ECDSA signature
+        /// over `permit_hash`, valid until `deadline`. `owner` must be the
+        /// BLAKE2-256 hash of the signer's compressed public key, as for
+        /// Substrate ECDSA accounts. Each permit consumes the owner's nonce
+        #[ink(message)]
+        pub fn permit(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: Balance,
+            deadline: Timestamp,
+            signature: [u8; 65],
+        ) -> Result<()> {
+            self.ensure_not_paused()?;
+            if self.env().block_timestamp() > deadline {
+                return Err(Error::PermitExpired);
+            }
+            let hash = self.permit_hash(owner, spender, value, deadline);
+            let public_key = self
+                .env()
+                .ecdsa_recover(&signature, &hash)
+                .map_err(|_| Error::InvalidSignature)?;
+            let signer = AccountId::from(self.env().hash_bytes::<Blake2x256>(&public_key));
+            if signer != owner {
+                return Err(Error::InvalidSignature);
+            }
+            self.ensure_not_frozen(&owner)?;
+            if self.safe_approve && value != 0 && self.allowance(owner, spender) != 0 {
+                return Err(Error::UnsafeApproval);
+            }
+
+            let nonce = self.nonce_of(owner).checked_add(1).ok_or(Error::Overflow)?;
+            self.nonces.insert(owner, &nonce);
+            self.set_allowance(owner, spender, value);
+
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount: value,
+            });
+
+            Ok(())
+        }
+
+        /// Transfer tokens on behalf of another account
+        /// Follows checks-effects-interactions: every check runs first, then the
+        /// allowance is debited before any balance moves
+        /// Emits `Approval` with the remaining allowance so indexers stay in sync
+        /// A self-transfer (`from == to`) is checked but spends no allowance
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.ensure_not_paused()?;
+            self.ensure_not_large_transfer(value)?;
+            let caller = self.env().caller();
+            let allowance = self
+                .allowance(from, caller)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientAllowance)?;
+            self.ensure_can_transfer(&from, &to, value)?;
+            if from == to {
+                return Ok(());
+            }
+
+            self.set_allowance(from, caller, allowance);
+            self.env().emit_event(Approval {
+                owner: from,
+                spender: caller,
+                amount: allowance,
+            });
+            self.transfer_from_to(&from, &to, value)
+        }
+
+        /// Mint new tokens (owner only)
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.ensure_not_paused()?;
+            Self::ensure_not_zero(&to)?;
+            if let Some(limit) = self.max_mint_per_call {
+                if value > limit {
+                    return Err(Error::MintLimitExceeded);
+                }
+            }
+            self.mint_to(&to, value)
+        }
+
+        /// Create a linear vesting schedule minted to `beneficiary` as it
+        /// unlocks (owner only). Times are block timestamps in milliseconds
+        #[ink(message)]
+        pub fn create_vesting(
+            &mut self,
+            beneficiary: AccountId,
+            total: Balance,
+            start_ts: u64,
+            duration: u64,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            Self::ensure_not_zero(&beneficiary)?;
+            if self.vestings.contains(beneficiary) {
+                return Err(Error::VestingExists);
+            }
+
+            self.vestings.insert(
+                beneficiary,
+                &Vesting {
+                    total,
+                    start_ts,
+                    duration,
+                    claimed: 0,
+                },
+            );
+            self.env().emit_event(VestingCreated {
+                beneficiary,
+                total,
+                start_ts,
+                duration,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the vesting schedule of an account, if any
+        #[ink(message)]
+        pub fn vesting_of(&self, beneficiary: AccountId) -> Option<Vesting> {
+            self.vestings.get(beneficiary)
+        }
+
+        /// Returns the amount `beneficiary` could claim right now
+        #[ink(message)]
+        pub fn claimable(&self, beneficiary: AccountId) -> Balance {
+            self.vestings
+                .get(beneficiary)
+                .map(|vesting| {
+                    vesting
+                        .vested_at(self.env().block_timestamp())
+                        .saturating_sub(vesting.claimed)
+                })
+                .unwrap_or(0)
+        }
+
+        /// Mint the caller's vested but unclaimed tokens, returning the amount
+        #[ink(message)]
+        pub fn claim_vested(&mut self) -> Result<Balance> {
+            self.ensure_not_paused()?;
+            let caller = self.env().caller();
+            let mut vesting = self.vestings.get(caller).ok_or(Error::NoVesting)?;
+            let now = self.env().block_timestamp();
+            if now < vesting.start_ts {
+                return Err(Error::VestingNotStarted);
+            }
+
+            let amount = vesting.vested_at(now).saturating_sub(vesting.claimed);
+            if amount > 0 {
+                self.mint_to(&caller, amount)?;
+                vesting.claimed = vesting.claimed.saturating_add(amount);
+                self.vestings.insert(caller, &vesting);
+            }
+
+            Ok(amount)
+        }
+
+        /// Internal mint, shared by `mint` and vesting claims
+        fn mint_to(&mut self, to: &AccountId, value: Balance) -> Result<()> {
+            let total_supply = self
+                .total_supply
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            if let Some(cap) = self.max_supply {
+                if total_supply > cap {
+                    return Err(Error::CapExceeded);
+                }
+            }
+            self.ensure_within_max_balance(to, value)?;
+
+            let balance = self
+                .balance_of(*to)
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            self.set_balance(to, balance);
+            self.total_supply = total_supply;
+
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(*to),
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Burn tokens
+        /// Allowed while paused so holders can always exit
+        #[ink(message)]
+        pub fn burn(&mut self, value: Balance) -> Result<()> {
+            let caller = self.env().caller();
+            let balance = self
+                .balance_of(caller)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientBalance)?;
+            let total_supply = self
+                .total_supply
+                .checked_sub(value)
+                .ok_or(Error::SupplyUnderflow)?;
+
+            self.set_balance(&caller, balance);
+            self.total_supply = total_supply;
+            self.total_burned = self.total_burned.saturating_add(value);
+
+            self.env().emit_event(Transfer {
+                from: Some(caller),
+                to: None,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Burn tokens from another account using the caller's allowance
+        /// Emits `Approval` with the remaining allowance
+        #[ink(message)]
+        pub fn burn_from(&mut self, from: AccountId, value: Balance) -> Result<()> {
+            let caller = self.env().caller();
+            let allowance = self
+                .allowance(from, caller)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientAllowance)?;
+            let balance = self
+                .balance_of(from)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientBalance)?;
+            let total_supply = self
+                .total_supply
+                .checked_sub(value)
+                .ok_or(Error::SupplyUnderflow)?;
+
+            self.set_allowance(from, caller, allowance);
+            self.env().emit_event(Approval {
+                owner: from,
+                spender: caller,
+                amount: allowance,
+            });
+            self.set_balance(&from, balance);
+            self.total_supply = total_supply;
+            self.total_burned = self.total_burned.saturating_add(value);
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: None,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Transfer ownership to a new account (owner only)
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            let previous = self.ensure_owner()?;
+            self.owner = Some(new_owner);
+
+            self.env().emit_event(OwnershipTransferred {
+                previous,
+                new: Some(new_owner),
+            });
+
+            Ok(())
+        }
+
+        /// Give up ownership permanently, disabling minting (owner only)
+        #[ink(message)]
+        pub fn renounce_ownership(&mut self) -> Result<()> {
+            let previous = self.ensure_owner()?;
+            self.owner = None;
+
+            self.env().emit_event(OwnershipTransferred {
+                previous,
+                new: None,
+            });
+
+            Ok(())
+        }
+
+        /// Halt transfers, approvals and minting (owner only)
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            let caller = self.ensure_owner()?;
+            self.paused = true;
+            self.env().emit_event(Paused { by: caller });
+            Ok(())
+        }
+
+        /// Resume transfers, approvals and minting (owner only)
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            let caller = self.ensure_owner()?;
+            self.paused = false;
+            self.env().emit_event(Unpaused { by: caller });
+            Ok(())
+        }
+
+        /// Returns whether the token is paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Block an account from sending, receiving and approving (owner only)
+        #[ink(message)]
+        pub fn freeze(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.frozen.insert(account, &true);
+            self.env().emit_event(AccountFrozen { account });
+            Ok(())
+        }
+
+        /// Lift a freeze on an account (owner only)
+        #[ink(message)]
+        pub fn unfreeze(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.frozen.remove(account);
+            self.env().emit_event(AccountUnfrozen { account });
+            Ok(())
+        }
+
+        /// Returns whether an account is frozen
+        #[ink(message)]
+        pub fn is_frozen(&self, account: AccountId) -> bool {
+            self.frozen.get(account).unwrap_or(false)
+        }
+
+        /// Recover tokens accidentally sent to this contract's own account (owner only)
+        #[ink(message)]
+        pub fn recover(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            let contract = self.env().account_id();
+            self.transfer_from_to(&contract, &to, value)
+        }
+
+        /// Move tokens out of any account regardless of allowance (owner only)
+        /// Intended for clawbacks, so it ignores pause, freezes, fees and the
+        /// balance cap. Emits `Transfer` followed by a `ForcedTransfer` audit event
+        #[ink(message)]
+        pub fn force_transfer(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let by = self.ensure_owner()?;
+            Self::ensure_not_zero(&from)?;
+            Self::ensure_not_zero(&to)?;
+
+            let from_balance = self
+                .balance_of(from)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientBalance)?;
+            self.set_balance(&from, from_balance);
+
+            let to_balance = self
+                .balance_of(to)
+                .checked_add(value)
+                .ok_or(Error::Overflow)?;
+            self.set_balance(&to, to_balance);
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+            self.env().emit_event(ForcedTransfer {
+                from,
+                to,
+                value,
+                by,
+            });
+
+            Ok(())
+        }
+
+        /// Record the current total supply under a new snapshot id (owner only)
+        /// Balances are checkpointed lazily, the first time they change afterwards
+        #[ink(message)]
+        pub fn snapshot(&mut self) -> Result<u32> {
+            self.ensure_owner()?;
+            let id = self
+                .current_snapshot_id
+                .checked_add(1)
+                .ok_or(Error::Overflow)?;
+            self.current_snapshot_id = id;
+            self.snapshot_supply.insert(id, &self.total_supply);
+            self.env().emit_event(Snapshot { id });
+            Ok(id)
+        }
+
+        /// Returns the id of the most recent snapshot (0 if none)
+        #[ink(message)]
+        pub fn current_snapshot_id(&self) -> u32 {
+            self.current_snapshot_id
+        }
+
+        /// Returns the total supply recorded by a snapshot
+        #[ink(message)]
+        pub fn total_supply_at(&self, snapshot_id: u32) -> Result<Balance> {
+            self.ensure_valid_snapshot(snapshot_id)?;
+            Ok(self.snapshot_supply.get(snapshot_id).unwrap_or(0))
+        }
+
+        /// Returns an account's balance as of a snapshot
+        /// Reads the first checkpoint at or after `snapshot_id`, found by binary
+        /// search over the account's checkpoint ids, falling back to the live
+        /// balance when the account has not changed since
+        #[ink(message)]
+        pub fn balance_of_at(&self, account: AccountId, snapshot_id: u32) -> Result<Balance> {
+            self.ensure_valid_snapshot(snapshot_id)?;
+            let ids = self.checkpoint_ids.get(account).unwrap_or_default();
+            let index = ids.partition_point(|&id| id < snapshot_id);
+            match ids.get(index) {
+                Some(&id) => Ok(self.balance_checkpoints.get((account, id)).unwrap_or(0)),
+                None => Ok(self.balance_of(account)),
+            }
+        }
+
+        /// Internal snapshot id check
+        fn ensure_valid_snapshot(&self, snapshot_id: u32) -> Result<()> {
+            if snapshot_id == 0 || snapshot_id > self.current_snapshot_id {
+                return Err(Error::InvalidSnapshot);
+            }
+            Ok(())
+        }
+
+        /// Internal zero-address check
+        fn ensure_not_zero(account: &AccountId) -> Result<()> {
+            if *account == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+            Ok(())
+        }
+
+        /// Internal freeze check
+        fn ensure_not_frozen(&self, account: &AccountId) -> Result<()> {
+            if self.is_frozen(*account) {
+                return Err(Error::AccountFrozen);
+            }
+            Ok(())
+        }
+
+        /// Internal pause check
+        fn ensure_not_paused(&self) -> Result<()> {
+            if self.paused {
+                return Err(Error::Paused);
+            }
+            Ok(())
+        }
+
+        /// Whether `value` must go through the large transfer timelock
+        fn is_large_transfer(&self, value: Balance) -> bool {
+            self.large_transfer_threshold
+                .is_some_and(|threshold| value >= threshold)
+        }
+
+        /// Internal check that `value` may move without the timelock
+        fn ensure_not_large_transfer(&self, value: Balance) -> Result<()> {
+            if self.is_large_transfer(value) {
+                return Err(Error::TimelockRequired);
+            }
+            Ok(())
+        }
+
+        /// Internal allowance writer that keeps `spenders_of` in sync,
+        /// dropping spenders whose allowance reaches zero
+        fn set_allowance(&mut self, owner: AccountId, spender: AccountId, value: Balance) {
+            let mut spenders = self.spenders_of.get(owner).unwrap_or_default();
+            let listed = spenders.contains(&spender);
+            if value > 0 && !listed {
+                spenders.push(spender);
+                self.spenders_of.insert(owner, &spenders);
+            } else if value == 0 && listed {
+                spenders.retain(|account| *account != spender);
+                if spenders.is_empty() {
+                    self.spenders_of.remove(owner);
+                } else {
+                    self.spenders_of.insert(owner, &spenders);
+                }
+            }
+            self.allowances.insert((owner, spender), &value);
+        }
+
+        /// Internal owner check, returns the caller on success
+        fn ensure_owner(&self) -> Result<AccountId> {
+            let caller = self.env().caller();
+            if self.owner != Some(caller) {
+                return Err(Error::Unauthorized);
+            }
+            Ok(caller)
+        }
+
+        /// Internal balance writer that keeps `holder_count` and snapshot
+        /// checkpoints in sync
+        fn set_balance(&mut self, account: &AccountId, balance: Balance) {
+            let previous = self.balance_of(*account);
+            let snapshot_id = self.current_snapshot_id;
+            let mut ids = self.checkpoint_ids.get(account).unwrap_or_default();
+            if ids.last().copied().unwrap_or(0) < snapshot_id {
+                self.balance_checkpoints
+                    .insert((*account, snapshot_id), &previous);
+                ids.push(snapshot_id);
+                self.checkpoint_ids.insert(*account, &ids);
+            }
+            if previous == 0 && balance > 0 {
+                self.holder_count = self.holder_count.saturating_add(1);
+            } else if previous > 0 && balance == 0 {
+                self.holder_count = self.holder_count.saturating_sub(1);
+            }
+            self.balances.insert(*account, &balance);
+        }
+
+        /// Internal transfer precondition checks, without touching state
+        fn ensure_can_transfer(
+            &self,
+            from: &AccountId,
+            to: &AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            Self::ensure_not_zero(to)?;
+            self.ensure_not_frozen(from)?;
+            self.ensure_not_frozen(to)?;
+            if self.balance_of(*from) < value {
+                return Err(Error::InsufficientBalance);
+            }
+            if from != to {
+                let received = value - self.transfer_fee(from, to, value);
+                self.ensure_within_max_balance(to, received)?;
+            }
+            Ok(())
+        }
+
+        /// Internal anti-whale check on a recipient receiving `value` more
+        fn ensure_within_max_balance(&self, account: &AccountId, value: Balance) -> Result<()> {
+            let Some(cap) = self.max_balance else {
+                return Ok(());
+            };
+            if self.owner == Some(*account) || *account == self.treasury {
+                return Ok(());
+            }
+            if self.balance_of(*account).saturating_add(value) > cap {
+                return Err(Error::MaxBalanceExceeded);
+            }
+            Ok(())
+        }
+
+        /// Internal transfer helper
+        /// Rejects the zero address as recipient. A self-transfer passes the
+        /// same checks (including the balance) but is a no-op: no fee is taken
+        /// and no `Transfer` event is emitted
+        fn transfer_from_to(
+            &mut self,
+            from: &AccountId,
+            to: &AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.ensure_can_transfer(from, to, value)?;
+            if from == to {
+                return Ok(());
+            }
+            let fee = self.transfer_fee(from, to, value);
+
+            let from_balance = self
+                .balance_of(*from)
+                .checked_sub(value)
+                .ok_or(Error::InsufficientBalance)?;
+            self.set_balance(from, from_balance);
+
+            let to_balance = self
+                .balance_of(*to)
+                .checked_add(value - fee)
+                .ok_or(Error::Overflow)?;
+            self.set_balance(to, to_balance);
+
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                value: value - fee,
+            });
+
+            if fee > 0 {
+                let treasury = self.treasury;
+                let treasury_balance = self
+                    .balance_of(treasury)
+                    .checked_add(fee)
+                    .ok_or(Error::Overflow)?;
+                self.set_balance(&treasury, treasury_balance);
+
+                self.env().emit_event(Transfer {
+                    from: Some(*from),
+                    to: Some(treasury),
+                    value: fee,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Internal fee calculation; transfers touching the treasury are exempt
+        fn transfer_fee(&self, from: &AccountId, to: &AccountId, value: Balance) -> Balance {
+            if self.fee_bps == 0 || *from == self.treasury || *to == self.treasury {
+                return 0;
+            }
+            // Split the product so `value * fee_bps` cannot overflow
+            let bps = Balance::from(self.fee_bps);
+            (value / 10_000) * bps + (value % 10_000) * bps / 10_000
+        }
+    }
+
+    impl PSP22 for Erc20 {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            Erc20::balance_of(self, owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            Erc20::allowance(self, owner, spender)
+        }
+
+        #[ink(message)]
+        fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22Error> {
+            Erc20::transfer(self, to, value).map_err(Into::into)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22Error> {
+            Erc20::transfer_from(self, from, to, value).map_err(Into::into)
+        }
+
+        #[ink(message)]
+        fn approve(
+            &mut self,
+            spender: AccountId,
+            value: Balance,
+        ) -> core::result::Result<(), PSP22Error> {
+            Erc20::approve(self, spender, value).map_err(Into::into)
+        }
+
+        #[ink(message)]
+        fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> core::result::Result<(), PSP22Error> {
+            Erc20::increase_allowance(self, spender, delta_value).map_err(Into::into)
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> core::result::Result<(), PSP22Error> {
+            Erc20::decrease_allowance(self, spender, delta_value).map_err(Into::into)
+        }
+    }
+
+    impl PSP22Metadata for Erc20 {
+        #[ink(message)]
+        fn token_name(&self) -> Option<String> {
+            Some(self.name.clone())
+        }
+
+        #[ink(message)]
+        fn token_symbol(&self) -> Option<String> {
+            Some(self.symbol.clone())
+        }
+
+        #[ink(message)]
+        fn token_decimals(&self) -> u8 {
+            self.decimals
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn new_works() {
+            let erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.name(), "TestToken");
+            assert_eq!(erc20.symbol(), "TST");
+            assert_eq!(erc20.decimals(), 18);
+        }
+
+        #[ink::test]
+        fn transfer_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.balance_of(accounts.alice), 900);
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn approve_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.approve(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn increase_decrease_allowance_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.increase_allowance(accounts.bob, 100).is_ok());
+            assert!(erc20.increase_allowance(accounts.bob, 50).is_ok());
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 150);
+
+            assert!(erc20.decrease_allowance(accounts.bob, 120).is_ok());
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 30);
+        }
+
+        #[ink::test]
+        fn decrease_allowance_underflow_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.approve(accounts.bob, 10).is_ok());
+            assert_eq!(
+                erc20.decrease_allowance(accounts.bob, 11),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn burn_from_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 300).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(erc20.burn_from(accounts.alice, 200).is_ok());
+
+            assert_eq!(erc20.balance_of(accounts.alice), 800);
+            assert_eq!(erc20.total_supply(), 800);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn burn_from_insufficient_allowance_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 50).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.burn_from(accounts.alice, 51),
+                Err(Error::InsufficientAllowance)
+            );
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn burn_from_insufficient_balance_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 5000).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.burn_from(accounts.alice, 1001),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 5000);
+        }
+
+        #[ink::test]
+        fn mint_up_to_cap_works() {
+            let mut erc20 = Erc20::new_capped(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+                Some(1500),
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(erc20.max_supply(), Some(1500));
+            assert!(erc20.mint(accounts.bob, 500).is_ok());
+            assert_eq!(erc20.total_supply(), 1500);
+        }
+
+        #[ink::test]
+        fn mint_over_cap_fails() {
+            let mut erc20 = Erc20::new_capped(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+                Some(1500),
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(erc20.mint(accounts.bob, 501), Err(Error::CapExceeded));
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn mint_uncapped_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(erc20.max_supply(), None);
+            assert!(erc20.mint(accounts.bob, 1_000_000).is_ok());
+            assert_eq!(erc20.total_supply(), 1_001_000);
+        }
+
+        #[ink::test]
+        fn mint_overflow_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                Balance::MAX - 10,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(erc20.mint(accounts.bob, 11), Err(Error::Overflow));
+            assert_eq!(erc20.total_supply(), Balance::MAX - 10);
+            assert!(erc20.mint(accounts.bob, 10).is_ok());
+            assert_eq!(erc20.total_supply(), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn burn_insufficient_balance_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+
+            assert_eq!(erc20.burn(1001), Err(Error::InsufficientBalance));
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn transfer_ownership_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.transfer_ownership(accounts.bob).is_ok());
+            assert_eq!(erc20.owner(), Some(accounts.bob));
+            assert_eq!(erc20.mint(accounts.alice, 10), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(erc20.mint(accounts.bob, 10).is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+        }
+
+        #[ink::test]
+        fn renounce_ownership_disables_minting() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.renounce_ownership().is_ok());
+            assert_eq!(erc20.owner(), None);
+            assert_eq!(erc20.mint(accounts.alice, 1), Err(Error::Unauthorized));
+            assert_eq!(
+                erc20.transfer_ownership(accounts.alice),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn transfers_fail_while_paused() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.pause().is_ok());
+            assert!(erc20.is_paused());
+            assert_eq!(erc20.transfer(accounts.bob, 100), Err(Error::Paused));
+            assert_eq!(erc20.approve(accounts.bob, 100), Err(Error::Paused));
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 100),
+                Err(Error::Paused)
+            );
+            assert_eq!(erc20.mint(accounts.bob, 100), Err(Error::Paused));
+
+            // Burning stays available while paused
+            assert!(erc20.burn(10).is_ok());
+
+            assert!(erc20.unpause().is_ok());
+            assert!(!erc20.is_paused());
+            assert!(erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn pause_not_owner_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(erc20.pause(), Err(Error::Unauthorized));
+            assert_eq!(erc20.unpause(), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn frozen_sender_cannot_transfer() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.freeze(accounts.alice).is_ok());
+            assert!(erc20.is_frozen(accounts.alice));
+            assert_eq!(erc20.transfer(accounts.bob, 100), Err(Error::AccountFrozen));
+            assert_eq!(erc20.approve(accounts.bob, 100), Err(Error::AccountFrozen));
+
+            assert!(erc20.unfreeze(accounts.alice).is_ok());
+            assert!(!erc20.is_frozen(accounts.alice));
+            assert!(erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn frozen_recipient_cannot_receive() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.freeze(accounts.bob).is_ok());
+            assert_eq!(erc20.transfer(accounts.bob, 100), Err(Error::AccountFrozen));
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+
+            assert!(erc20.unfreeze(accounts.bob).is_ok());
+            assert!(erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn freeze_not_owner_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(erc20.freeze(accounts.alice), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn batch_transfer_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let recipients = vec![
+                (accounts.bob, 100),
+                (accounts.charlie, 200),
+                (accounts.django, 300),
+            ];
+            assert!(erc20.batch_transfer(recipients).is_ok());
+
+            assert_eq!(erc20.balance_of(accounts.alice), 400);
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.balance_of(accounts.charlie), 200);
+            assert_eq!(erc20.balance_of(accounts.django), 300);
+            // One mint event from the constructor plus one per recipient
+            assert_eq!(ink::env::test::recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn batch_transfer_insufficient_balance_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let recipients = vec![(accounts.bob, 600), (accounts.charlie, 500)];
+            assert_eq!(
+                erc20.batch_transfer(recipients),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn batch_transfer_empty_is_noop() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.batch_transfer(Vec::new()).is_ok());
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
+
+        #[ink::test]
+        fn transfer_to_zero_address_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let zero = AccountId::from([0u8; 32]);
+
+            assert_eq!(erc20.transfer(zero, 100), Err(Error::ZeroAddress));
+            assert_eq!(erc20.balance_of(zero), 0);
+        }
+
+        #[ink::test]
+        fn mint_to_zero_address_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+
+            assert_eq!(
+                erc20.mint(AccountId::from([0u8; 32]), 100),
+                Err(Error::ZeroAddress)
+            );
+            assert_eq!(erc20.total_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn self_transfer_keeps_balance() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.transfer(accounts.alice, 400).is_ok());
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
+
+        #[ink::test]
+        fn psp22_trait_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(PSP22Metadata::token_name(&erc20), Some("TestToken".to_string()));
+            assert_eq!(PSP22Metadata::token_symbol(&erc20), Some("TST".to_string()));
+            assert_eq!(PSP22Metadata::token_decimals(&erc20), 18);
+            assert_eq!(PSP22::total_supply(&erc20), 1000);
+
+            assert!(PSP22::transfer(&mut erc20, accounts.bob, 100, Vec::new()).is_ok());
+            assert_eq!(PSP22::balance_of(&erc20, accounts.bob), 100);
+
+            assert!(PSP22::approve(&mut erc20, accounts.bob, 50).is_ok());
+            assert!(PSP22::increase_allowance(&mut erc20, accounts.bob, 25).is_ok());
+            assert!(PSP22::decrease_allowance(&mut erc20, accounts.bob, 5).is_ok());
+            assert_eq!(PSP22::allowance(&erc20, accounts.alice, accounts.bob), 70);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                PSP22::transfer_from(&mut erc20, accounts.alice, accounts.charlie, 70, Vec::new()),
+                Ok(())
+            );
+            assert_eq!(
+                PSP22::transfer_from(&mut erc20, accounts.alice, accounts.charlie, 1, Vec::new()),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(PSP22::balance_of(&erc20, accounts.charlie), 70);
+        }
+
+        #[ink::test]
+        fn total_burned_tracked() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20.burn(100).is_ok());
+            assert!(erc20.approve(accounts.bob, 50).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(erc20.burn_from(accounts.alice, 50).is_ok());
+
+            assert_eq!(erc20.get_total_burned(), 150);
+            assert_eq!(erc20.total_supply(), 850);
+        }
+
+        #[ink::test]
+        fn holder_count_tracked() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.get_holder_count(), 1);
+
+            // First receipt adds a holder
+            assert!(erc20.transfer(accounts.bob, 400).is_ok());
+            assert_eq!(erc20.get_holder_count(), 2);
+            assert!(erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.get_holder_count(), 2);
+
+            // Sending the full balance away removes a holder
+            assert!(erc20.transfer(accounts.charlie, 500).is_ok());
+            assert_eq!(erc20.get_holder_count(), 2);
+
+            assert!(erc20.mint(accounts.django, 10).is_ok());
+            assert_eq!(erc20.get_holder_count(), 3);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(erc20.burn(10).is_ok());
+            assert_eq!(erc20.get_holder_count(), 2);
+        }
+
+        #[ink::test]
+        fn safe_approve_requires_zeroing_first() {
+            let mut erc20 = Erc20::new_with_safe_approve(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+                true,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.safe_approve());
+
+            assert!(erc20.approve(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.approve(accounts.bob, 50), Err(Error::UnsafeApproval));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+
+            assert!(erc20.approve(accounts.bob, 0).is_ok());
+            assert!(erc20.approve(accounts.bob, 50).is_ok());
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn safe_approve_off_by_default() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(!erc20.safe_approve());
+
+            assert!(erc20.approve(accounts.bob, 100).is_ok());
+            assert!(erc20.approve(accounts.bob, 50).is_ok());
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn transfer_with_memo_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20
+                .transfer_with_memo(accounts.bob, 100, b"invoice #42".to_vec())
+                .is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            // Mint, Transfer and TransferMemo
+            assert_eq!(ink::env::test::recorded_events().count(), 3);
+
+            let memo = vec![0u8; MAX_MEMO_LEN];
+            assert!(erc20.transfer_with_memo(accounts.bob, 1, memo).is_ok());
+        }
+
+        #[ink::test]
+        fn transfer_with_memo_too_long_fails() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let memo = vec![0u8; MAX_MEMO_LEN + 1];
+            assert_eq!(
+                erc20.transfer_with_memo(accounts.bob, 100, memo),
+                Err(Error::MemoTooLong)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn mint_per_call_limit_enforced() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.max_mint_per_call(), None);
+
+            assert_eq!(erc20.set_max_mint_per_call(Some(100)), Ok(()));
+            assert_eq!(erc20.max_mint_per_call(), Some(100));
+            assert!(erc20.mint(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.mint(accounts.bob, 101), Err(Error::MintLimitExceeded));
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.total_supply(), 1100);
+        }
+
+        #[ink::test]
+        fn mint_without_per_call_limit_unchanged() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(erc20.set_max_mint_per_call(Some(10)), Ok(()));
+            assert_eq!(erc20.set_max_mint_per_call(None), Ok(()));
+            assert!(erc20.mint(accounts.bob, 1_000_000).is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 1_000_000);
+        }
+
+        #[ink::test]
+        fn set_max_mint_per_call_owner_only() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.set_max_mint_per_call(Some(1)),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_from_spends_allowance() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 300).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(erc20
+                .transfer_from(accounts.alice, accounts.charlie, 100)
+                .is_ok());
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 200);
+            assert_eq!(erc20.balance_of(accounts.charlie), 100);
+        }
+
+        #[ink::test]
+        fn failed_transfer_from_keeps_allowance() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 5000).is_ok());
+            assert!(erc20.freeze(accounts.django).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.charlie, 1001),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.django, 10),
+                Err(Error::AccountFrozen)
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 5000);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
+
+        #[ink::test]
+        fn spending_allowance_emits_remaining_amount() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 300).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(erc20
+                .transfer_from(accounts.alice, accounts.charlie, 100)
+                .is_ok());
+            assert!(erc20.burn_from(accounts.alice, 50).is_ok());
+
+            // Mint, approve, spend + transfer, spend + burn
+            let events: Vec<_> = ink::env::test::recorded_events().collect();
+            assert_eq!(events.len(), 6);
+            let spent = <Approval as scale::Decode>::decode(&mut &events[2].data[..]).unwrap();
+            assert_eq!(spent.owner, accounts.alice);
+            assert_eq!(spent.spender, accounts.bob);
+            assert_eq!(spent.amount, 200);
+            let burned = <Approval as scale::Decode>::decode(&mut &events[4].data[..]).unwrap();
+            assert_eq!(burned.amount, 150);
+        }
+
+        #[ink::test]
+        fn snapshot_preserves_past_balances() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.current_snapshot_id(), 0);
+
+            assert_eq!(erc20.snapshot(), Ok(1));
+            assert!(erc20.transfer(accounts.bob, 300).is_ok());
+            assert!(erc20.mint(accounts.bob, 200).is_ok());
+
+            assert_eq!(erc20.balance_of(accounts.alice), 700);
+            assert_eq!(erc20.balance_of_at(accounts.alice, 1), Ok(1000));
+            assert_eq!(erc20.balance_of_at(accounts.bob, 1), Ok(0));
+            assert_eq!(erc20.total_supply_at(1), Ok(1000));
+
+            assert_eq!(erc20.snapshot(), Ok(2));
+            assert_eq!(erc20.snapshot(), Ok(3));
+            assert!(erc20.transfer(accounts.bob, 100).is_ok());
+
+            assert_eq!(erc20.balance_of_at(accounts.alice, 1), Ok(1000));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 2), Ok(700));
+            assert_eq!(erc20.balance_of_at(accounts.bob, 2), Ok(500));
+            assert_eq!(erc20.balance_of_at(accounts.bob, 3), Ok(500));
+            assert_eq!(erc20.balance_of(accounts.bob), 600);
+            assert_eq!(erc20.total_supply_at(2), Ok(1200));
+        }
+
+        #[ink::test]
+        fn balance_of_at_across_sparse_checkpoints() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            // Alice changes only after snapshots 10 and 40 of 50
+            for id in 1..=50 {
+                assert_eq!(erc20.snapshot(), Ok(id));
+                if id == 10 || id == 40 {
+                    assert!(erc20.transfer(accounts.bob, 100).is_ok());
+                }
+            }
+
+            assert_eq!(erc20.balance_of_at(accounts.alice, 1), Ok(1000));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 10), Ok(1000));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 11), Ok(900));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 40), Ok(900));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 41), Ok(800));
+            assert_eq!(erc20.balance_of_at(accounts.alice, 50), Ok(800));
+            assert_eq!(erc20.balance_of_at(accounts.bob, 25), Ok(100));
+            assert_eq!(erc20.balance_of_at(accounts.charlie, 50), Ok(0));
+        }
+
+        #[ink::test]
+        fn snapshot_rejects_unknown_ids_and_non_owner() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(erc20.total_supply_at(0), Err(Error::InvalidSnapshot));
+            assert_eq!(
+                erc20.balance_of_at(accounts.alice, 1),
+                Err(Error::InvalidSnapshot)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(erc20.snapshot(), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn recover_tokens_sent_to_contract() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            // The contract's own account; set before instantiating since the
+            // off-chain environment keys storage by callee
+            let contract = accounts.frank;
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+
+            assert!(erc20.transfer(contract, 250).is_ok());
+            assert_eq!(
+                erc20.recover(accounts.alice, 251),
+                Err(Error::InsufficientBalance)
+            );
+            assert!(erc20.recover(accounts.alice, 250).is_ok());
+
+            assert_eq!(erc20.balance_of(contract), 0);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
+
+        #[ink::test]
+        fn recover_owner_only() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let contract = accounts.frank;
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            assert!(erc20.transfer(contract, 250).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(erc20.recover(accounts.bob, 250), Err(Error::Unauthorized));
+            assert_eq!(erc20.balance_of(contract), 250);
+        }
+
+        #[ink::test]
+        fn circulating_supply_excludes_treasury() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.transfer(accounts.charlie, 400).is_ok());
+            assert_eq!(erc20.circulating_supply(), 1000);
+
+            assert!(erc20.exclude_from_circulation(accounts.charlie).is_ok());
+            assert!(erc20.exclude_from_circulation(accounts.charlie).is_ok());
+            assert!(erc20.is_excluded_from_circulation(accounts.charlie));
+            assert_eq!(erc20.circulating_supply(), 600);
+            assert_eq!(erc20.total_supply(), 1000);
+
+            assert!(erc20.include_in_circulation(accounts.charlie).is_ok());
+            assert!(!erc20.is_excluded_from_circulation(accounts.charlie));
+            assert_eq!(erc20.circulating_supply(), 1000);
+        }
+
+        #[ink::test]
+        fn exclude_from_circulation_owner_only() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.exclude_from_circulation(accounts.bob),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_and_call_to_account_skips_callback() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(erc20
+                .transfer_and_call(accounts.bob, 100, b"hello".to_vec())
+                .is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.balance_of(accounts.alice), 900);
+        }
+
+        // The off-chain environment cannot execute other contracts, so this only
+        // checks that a contract recipient is called. How an acknowledging or
+        // rejecting receiver is handled is not covered by any test
+        #[ink::test]
+        #[should_panic(expected = "off-chain environment does not support contract invocation")]
+        fn transfer_and_call_to_contract_invokes_receiver() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_contract::<ink::env::DefaultEnvironment>(accounts.charlie);
+
+            let _ = erc20.transfer_and_call(accounts.charlie, 100, Vec::new());
+        }
+
+        #[ink::test]
+        fn vesting_unlocks_linearly() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20
+                .create_vesting(accounts.bob, 500, 10_000, 1_000)
+                .is_ok());
+            assert_eq!(
+                erc20.create_vesting(accounts.bob, 1, 0, 1),
+                Err(Error::VestingExists)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(9_999);
+            assert_eq!(erc20.claim_vested(), Err(Error::VestingNotStarted));
+
+            // Halfway through the schedule
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10_500);
+            assert_eq!(erc20.claimable(accounts.bob), 250);
+            assert_eq!(erc20.claim_vested(), Ok(250));
+            assert_eq!(erc20.balance_of(accounts.bob), 250);
+            assert_eq!(erc20.claim_vested(), Ok(0));
+
+            // Fully vested, and capped at the total afterwards
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(20_000);
+            assert_eq!(erc20.claimable(accounts.bob), 250);
+            assert_eq!(erc20.claim_vested(), Ok(250));
+            assert_eq!(erc20.balance_of(accounts.bob), 500);
+            assert_eq!(erc20.total_supply(), 1500);
+            assert_eq!(erc20.vesting_of(accounts.bob).unwrap().claimed, 500);
+        }
+
+        #[ink::test]
+        fn vesting_requires_schedule_and_owner() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(erc20.claim_vested(), Err(Error::NoVesting));
+            assert_eq!(
+                erc20.create_vesting(accounts.bob, 500, 0, 1_000),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_fee_routed_to_treasury() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                10_000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_transfer_fee(250, accounts.eve), Ok(()));
+            assert_eq!(erc20.fee_bps(), 250);
+            assert_eq!(erc20.treasury(), accounts.eve);
+
+            assert!(erc20.transfer(accounts.bob, 1_000).is_ok());
+            assert_eq!(erc20.balance_of(accounts.alice), 9_000);
+            assert_eq!(erc20.balance_of(accounts.bob), 975);
+            assert_eq!(erc20.balance_of(accounts.eve), 25);
+            assert_eq!(erc20.total_supply(), 10_000);
+            // Mint, net transfer and fee transfer
+            assert_eq!(ink::env::test::recorded_events().count(), 3);
+
+            // The treasury itself moves tokens fee-free
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert!(erc20.transfer(accounts.bob, 25).is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 1_000);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+        }
+
+        #[ink::test]
+        fn zero_fee_transfer_unchanged() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.fee_bps(), 0);
+
+            assert!(erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+        }
+
+        #[ink::test]
+        fn transfer_fee_capped() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(
+                erc20.set_transfer_fee(MAX_FEE_BPS + 1, accounts.eve),
+                Err(Error::FeeTooHigh)
+            );
+            assert_eq!(erc20.set_transfer_fee(MAX_FEE_BPS, accounts.eve), Ok(()));
+        }
+
+        #[ink::test]
+        fn max_balance_caps_recipients() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_max_balance(Some(300)), Ok(()));
+            assert_eq!(erc20.max_balance(), Some(300));
+
+            assert!(erc20.transfer(accounts.bob, 300).is_ok());
+            assert_eq!(
+                erc20.transfer(accounts.bob, 1),
+                Err(Error::MaxBalanceExceeded)
+            );
+            assert_eq!(erc20.mint(accounts.bob, 1), Err(Error::MaxBalanceExceeded));
+            assert_eq!(erc20.balance_of(accounts.bob), 300);
+        }
+
+        #[ink::test]
+        fn max_balance_exempts_owner_and_treasury() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_transfer_fee(0, accounts.eve), Ok(()));
+            assert_eq!(erc20.set_max_balance(Some(300)), Ok(()));
+
+            assert!(erc20.mint(accounts.alice, 500).is_ok());
+            assert!(erc20.transfer(accounts.eve, 400).is_ok());
+            assert_eq!(erc20.balance_of(accounts.alice), 1100);
+            assert_eq!(erc20.balance_of(accounts.eve), 400);
+        }
+
+        #[ink::test]
+        fn large_transfer_timelock_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_large_transfer_timelock(Some(500), 1_000), Ok(()));
+
+            assert!(erc20.transfer(accounts.bob, 499).is_ok());
+            assert_eq!(
+                erc20.transfer(accounts.bob, 500),
+                Err(Error::TimelockRequired)
+            );
+            assert_eq!(
+                erc20.request_large_transfer(accounts.bob, 499),
+                Err(Error::BelowLargeThreshold)
+            );
 
-            self.env().emit_event(Transfer {
-                from: Some(*from),
-                to: Some(*to),
-                value,
-            });
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            let id = erc20.request_large_transfer(accounts.bob, 500).unwrap();
+            assert_eq!(erc20.pending_transfer(id).unwrap().unlock_ts, 1_100);
 
-            Ok(())
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_099);
+            assert_eq!(
+                erc20.execute_large_transfer(id),
+                Err(Error::TimelockNotElapsed)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 499);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_100);
+            assert_eq!(erc20.execute_large_transfer(id), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 999);
+            assert_eq!(erc20.pending_transfer(id), None);
+            assert_eq!(
+                erc20.execute_large_transfer(id),
+                Err(Error::NoPendingTransfer)
+            );
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        #[ink::test]
+        fn large_transfer_only_sender_executes_or_cancels() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_large_transfer_timelock(Some(500), 0), Ok(()));
+            let id = erc20.request_large_transfer(accounts.bob, 600).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(erc20.execute_large_transfer(id), Err(Error::Unauthorized));
+            assert_eq!(erc20.cancel_large_transfer(id), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(erc20.cancel_large_transfer(id), Ok(()));
+            assert_eq!(
+                erc20.execute_large_transfer(id),
+                Err(Error::NoPendingTransfer)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+        }
 
         #[ink::test]
-        fn new_works() {
-            let erc20 = Erc20::new(
+        fn large_transfer_timelock_applies_to_batch_transfer() {
+            let mut erc20 = Erc20::new(
                 "TestToken".to_string(),
                 "TST".to_string(),
                 18,
                 1000,
             );
-            assert_eq!(erc20.total_supply(), 1000);
-            assert_eq!(erc20.name(), "TestToken");
-            assert_eq!(erc20.symbol(), "TST");
-            assert_eq!(erc20.decimals(), 18);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_large_transfer_timelock(Some(500), 1_000), Ok(()));
+
+            assert_eq!(
+                erc20.batch_transfer(vec![(accounts.bob, 500)]),
+                Err(Error::TimelockRequired)
+            );
+            assert_eq!(
+                erc20.batch_transfer(vec![(accounts.bob, 100), (accounts.charlie, 600)]),
+                Err(Error::TimelockRequired)
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert!(erc20
+                .batch_transfer(vec![(accounts.bob, 499), (accounts.charlie, 499)])
+                .is_ok());
         }
 
         #[ink::test]
-        fn transfer_works() {
+        fn large_transfer_timelock_applies_to_transfer_from() {
             let mut erc20 = Erc20::new(
                 "TestToken".to_string(),
                 "TST".to_string(),
@@ -260,14 +2853,85 @@ mod erc20 {
                 1000,
             );
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_large_transfer_timelock(Some(500), 1_000), Ok(()));
+            assert!(erc20.approve(accounts.bob, 1000).is_ok());
 
-            assert!(erc20.transfer(accounts.bob, 100).is_ok());
-            assert_eq!(erc20.balance_of(accounts.alice), 900);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.bob, 500),
+                Err(Error::TimelockRequired)
+            );
+            assert_eq!(
+                PSP22::transfer_from(&mut erc20, accounts.alice, accounts.bob, 500, Vec::new()),
+                Err(PSP22Error::from(Error::TimelockRequired))
+            );
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 1000);
+            assert!(erc20
+                .transfer_from(accounts.alice, accounts.bob, 499)
+                .is_ok());
+        }
+
+        #[ink::test]
+        fn force_transfer_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.transfer(accounts.bob, 300).is_ok());
+            assert!(erc20.freeze(accounts.bob).is_ok());
+
+            assert_eq!(
+                erc20.force_transfer(accounts.bob, accounts.charlie, 200),
+                Ok(())
+            );
             assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.balance_of(accounts.charlie), 200);
+
+            // Mint, transfer, freeze, forced transfer + audit
+            let events: Vec<_> = ink::env::test::recorded_events().collect();
+            assert_eq!(events.len(), 5);
+            let forced =
+                <ForcedTransfer as scale::Decode>::decode(&mut &events[4].data[..]).unwrap();
+            assert_eq!(forced.from, accounts.bob);
+            assert_eq!(forced.to, accounts.charlie);
+            assert_eq!(forced.value, 200);
+            assert_eq!(forced.by, accounts.alice);
+
+            assert_eq!(
+                erc20.force_transfer(accounts.bob, accounts.charlie, 101),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(
+                erc20.force_transfer(accounts.bob, AccountId::from([0u8; 32]), 1),
+                Err(Error::ZeroAddress)
+            );
         }
 
         #[ink::test]
-        fn approve_works() {
+        fn force_transfer_owner_only() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.force_transfer(accounts.alice, accounts.bob, 100),
+                Err(Error::Unauthorized)
+            );
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn burn_entire_supply_works() {
             let mut erc20 = Erc20::new(
                 "TestToken".to_string(),
                 "TST".to_string(),
@@ -275,9 +2939,339 @@ mod erc20 {
                 1000,
             );
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.transfer(accounts.bob, 400).is_ok());
+            assert!(erc20.burn(600).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(erc20.approve(accounts.charlie, 400).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(erc20.burn_from(accounts.bob, 400), Ok(()));
+
+            assert_eq!(erc20.total_supply(), 0);
+            assert_eq!(erc20.get_total_burned(), 1000);
+            assert_eq!(erc20.get_holder_count(), 0);
+            assert_eq!(erc20.burn(1), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn spender_enumeration_works() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.get_spenders(accounts.alice).is_empty());
 
             assert!(erc20.approve(accounts.bob, 100).is_ok());
-            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 100);
+            assert!(erc20.increase_allowance(accounts.charlie, 50).is_ok());
+            assert!(erc20.increase_allowance(accounts.bob, 20).is_ok());
+            assert_eq!(
+                erc20.get_spenders(accounts.alice),
+                vec![accounts.bob, accounts.charlie]
+            );
+            assert_eq!(
+                erc20.get_allowances(accounts.alice),
+                vec![(accounts.bob, 120), (accounts.charlie, 50)]
+            );
+
+            assert!(erc20.approve(accounts.bob, 0).is_ok());
+            assert_eq!(
+                erc20.get_allowances(accounts.alice),
+                vec![(accounts.charlie, 50)]
+            );
+        }
+
+        #[ink::test]
+        fn spender_pruned_when_allowance_spent() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 100).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(erc20
+                .transfer_from(accounts.alice, accounts.charlie, 100)
+                .is_ok());
+            assert!(erc20.get_spenders(accounts.alice).is_empty());
+        }
+
+        #[ink::test]
+        fn new_with_distribution_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let erc20 = Erc20::new_with_distribution(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                vec![
+                    (accounts.bob, 100),
+                    (accounts.charlie, 250),
+                    (accounts.django, 650),
+                ],
+            );
+
+            assert_eq!(erc20.balance_of(accounts.bob), 100);
+            assert_eq!(erc20.balance_of(accounts.charlie), 250);
+            assert_eq!(erc20.balance_of(accounts.django), 650);
+            assert_eq!(erc20.balance_of(accounts.alice), 0);
+            assert_eq!(erc20.total_supply(), 1000);
+            assert_eq!(erc20.get_holder_count(), 3);
+            assert_eq!(erc20.owner(), Some(accounts.alice));
+            assert_eq!(ink::env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "duplicate holder")]
+        fn new_with_distribution_rejects_duplicates() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            Erc20::new_with_distribution(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                vec![
+                    (accounts.bob, 100),
+                    (accounts.charlie, 1),
+                    (accounts.bob, 5),
+                ],
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "total supply overflow")]
+        fn new_with_distribution_rejects_overflow() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            Erc20::new_with_distribution(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                vec![(accounts.bob, Balance::MAX), (accounts.charlie, 1)],
+            );
+        }
+
+        #[ink::test]
+        fn new_accepts_valid_metadata() {
+            let erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            assert_eq!(erc20.decimals(), 18);
+
+            let erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                36,
+                1000,
+            );
+            assert_eq!(erc20.decimals(), 36);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "invalid decimals")]
+        fn new_rejects_excessive_decimals() {
+            Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                37,
+                1000,
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "empty name")]
+        fn new_rejects_empty_name() {
+            Erc20::new(
+                String::new(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "empty symbol")]
+        fn new_rejects_empty_symbol() {
+            Erc20::new(
+                "TestToken".to_string(),
+                String::new(),
+                18,
+                1000,
+            );
+        }
+
+        #[ink::test]
+        fn remaining_mintable_tracks_cap() {
+            let mut erc20 = Erc20::new_capped(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+                Some(1500),
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.remaining_mintable(), 500);
+
+            assert!(erc20.mint(accounts.bob, 200).is_ok());
+            assert_eq!(erc20.remaining_mintable(), 300);
+
+            assert!(erc20.mint(accounts.bob, 300).is_ok());
+            assert_eq!(erc20.remaining_mintable(), 0);
+            assert_eq!(erc20.mint(accounts.bob, 1), Err(Error::CapExceeded));
+
+            // Burning frees headroom again
+            assert!(erc20.burn(100).is_ok());
+            assert_eq!(erc20.remaining_mintable(), 100);
+        }
+
+        #[ink::test]
+        fn remaining_mintable_uncapped() {
+            let erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            assert_eq!(erc20.remaining_mintable(), Balance::MAX - 1000);
+        }
+
+        #[ink::test]
+        fn self_transfer_is_silent_no_op() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.set_transfer_fee(100, accounts.eve), Ok(()));
+
+            assert_eq!(erc20.transfer(accounts.alice, 1000), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            assert_eq!(erc20.balance_of(accounts.eve), 0);
+            // Only the constructor's mint was recorded
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+
+            assert_eq!(
+                erc20.transfer(accounts.alice, 1001),
+                Err(Error::InsufficientBalance)
+            );
+        }
+
+        #[ink::test]
+        fn self_transfer_from_keeps_allowance() {
+            let mut erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(erc20.approve(accounts.bob, 300).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.alice, 200),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 300);
+            assert_eq!(erc20.balance_of(accounts.alice), 1000);
+            // Only the constructor's mint and the approval were recorded
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+
+            assert_eq!(
+                erc20.transfer_from(accounts.alice, accounts.alice, 301),
+                Err(Error::InsufficientAllowance)
+            );
+        }
+
+        #[ink::test]
+        fn nonce_of_defaults_to_zero() {
+            let erc20 = Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            );
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(erc20.nonce_of(accounts.alice), 0);
+            assert_eq!(erc20.nonce_of(accounts.bob), 0);
+        }
+
+        /// Account of the secp256k1 key `[0x11; 32]`
+        const PERMIT_OWNER: [u8; 32] = [
+            45, 149, 235, 205, 199, 209, 229, 40, 235, 141, 195, 57, 165, 196, 71, 33, 211, 151,
+            67, 147, 43, 130, 163, 48, 63, 90, 34, 172, 30, 71, 13, 54,
+        ];
+
+        /// `PERMIT_OWNER`'s signature approving bob for 100 with nonce 0 and
+        /// deadline 1_000 on the contract at `[0x0C; 32]`
+        const PERMIT_SIGNATURE: [u8; 65] = [
+            49, 112, 212, 244, 250, 90, 161, 87, 4, 48, 148, 211, 157, 67, 33, 241, 167, 66, 48,
+            41, 22, 190, 199, 135, 14, 0, 126, 43, 25, 189, 42, 114, 14, 28, 153, 83, 19, 77, 254,
+            186, 105, 22, 113, 60, 207, 159, 224, 199, 116, 135, 6, 209, 148, 42, 144, 69, 170, 51,
+            175, 119, 41, 145, 233, 86, 0,
+        ];
+
+        fn permit_erc20() -> Erc20 {
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(AccountId::from([0x0C; 32]));
+            Erc20::new(
+                "TestToken".to_string(),
+                "TST".to_string(),
+                18,
+                1000,
+            )
+        }
+
+        #[ink::test]
+        fn permit_sets_allowance_and_increments_nonce() {
+            let mut erc20 = permit_erc20();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let owner = AccountId::from(PERMIT_OWNER);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 1_000, PERMIT_SIGNATURE),
+                Ok(())
+            );
+            assert_eq!(erc20.allowance(owner, accounts.bob), 100);
+            assert_eq!(erc20.nonce_of(owner), 1);
+
+            // The nonce moved on, so replaying the signature no longer matches
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 1_000, PERMIT_SIGNATURE),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(erc20.nonce_of(owner), 1);
+        }
+
+        #[ink::test]
+        fn permit_rejects_wrong_signer_and_expired_deadline() {
+            let mut erc20 = permit_erc20();
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let owner = AccountId::from(PERMIT_OWNER);
+
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 101, 1_000, PERMIT_SIGNATURE),
+                Err(Error::InvalidSignature)
+            );
+            assert_eq!(
+                erc20.permit(accounts.alice, accounts.bob, 100, 1_000, PERMIT_SIGNATURE),
+                Err(Error::InvalidSignature)
+            );
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1_001);
+            assert_eq!(
+                erc20.permit(owner, accounts.bob, 100, 1_000, PERMIT_SIGNATURE),
+                Err(Error::PermitExpired)
+            );
+            assert_eq!(erc20.allowance(owner, accounts.bob), 0);
+            assert_eq!(erc20.nonce_of(owner), 0);
         }
     }
 }
@@ -5,8 +5,17 @@
 
 #[ink::contract]
 mod counter {
+    use counter_core::{CounterError, CounterState};
+    use ink::prelude::{string::String, vec::Vec};
     use ink::storage::Mapping;
 
+    /// Semantic version of this contract build, taken from the crate version
+    /// Bump it whenever the storage layout changes
+    pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+    /// Most entries returned by one `get_all_user_increments` page
+    pub const MAX_USERS_PAGE: u32 = 100;
+
     /// Storage structure for the counter contract
     #[ink(storage)]
     pub struct Counter {
@@ -16,6 +25,22 @@ mod counter {
         owner: AccountId,
         /// Track increment counts per user
         user_increments: Mapping<AccountId, u32>,
+        /// Track decrement counts per user
+        user_decrements: Mapping<AccountId, u32>,
+        /// Every account that has incremented, indexed in order of first increment
+        users: Mapping<u32, AccountId>,
+        /// Number of entries in `users`
+        user_count: u32,
+        /// Whether increments and decrements are paused
+        paused: bool,
+        /// Optional upper ceiling for the counter value
+        max_value: Option<i32>,
+        /// Minimum interval between increments per user, in milliseconds (0 disables)
+        cooldown_ms: u64,
+        /// Timestamp of each user's last increment
+        last_action: Mapping<AccountId, u64>,
+        /// Whether `increment_by(n)` adds `n` to the caller's increment count instead of 1
+        count_bulk_as_actions: bool,
     }
 
     /// Event emitted when counter is incremented
@@ -41,6 +66,37 @@ mod counter {
         by: AccountId,
     }
 
+    /// Event emitted when the owner sets the counter directly
+    #[ink(event)]
+    pub struct ValueSet {
+        #[ink(topic)]
+        by: AccountId,
+        value: i32,
+    }
+
+    /// Event emitted when ownership is transferred
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        old_owner: AccountId,
+        #[ink(topic)]
+        new_owner: AccountId,
+    }
+
+    /// Event emitted when the contract is paused
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        by: AccountId,
+    }
+
+    /// Event emitted when the contract is unpaused
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        by: AccountId,
+    }
+
     /// Errors that can occur in the contract
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -51,23 +107,66 @@ mod counter {
         Overflow,
         /// Counter underflow
         Underflow,
+        /// Amount must be non-negative
+        InvalidAmount,
+        /// Zero address is not allowed
+        ZeroAddress,
+        /// Contract is paused
+        Paused,
+        /// Counter would exceed the configured maximum value
+        CapExceeded,
+        /// Caller must wait for the cooldown to elapse
+        CooldownActive,
     }
 
     /// Type alias for Result with our Error type
     pub type Result<T> = core::result::Result<T, Error>;
 
+    impl From<CounterError> for Error {
+        fn from(err: CounterError) -> Self {
+            match err {
+                CounterError::Overflow => Error::Overflow,
+                CounterError::Underflow | CounterError::BelowMin => Error::Underflow,
+                CounterError::AboveMax => Error::CapExceeded,
+            }
+        }
+    }
+
     impl Counter {
         /// Constructor initializes the counter with a starting value
         #[ink(constructor)]
         pub fn new(init_value: i32) -> Self {
+            Self::new_capped(init_value, None)
+        }
+
+        /// Constructor initializes the counter with a starting value and an optional cap
+        #[ink(constructor)]
+        pub fn new_capped(init_value: i32, max_value: Option<i32>) -> Self {
             let caller = Self::env().caller();
             Self {
                 value: init_value,
                 owner: caller,
                 user_increments: Mapping::default(),
+                user_decrements: Mapping::default(),
+                users: Mapping::default(),
+                user_count: 0,
+                paused: false,
+                max_value,
+                cooldown_ms: 0,
+                last_action: Mapping::default(),
+                count_bulk_as_actions: false,
             }
         }
 
+        /// Constructor choosing how `increment_by` feeds per-user stats:
+        /// as `amount` increments when `count_bulk_as_actions` is set, else as one
+        #[ink(constructor)]
+        pub fn new_with_bulk_counting(init_value: i32, count_bulk_as_actions: bool) -> Self {
+            let mut counter = Self::new(init_value);
+            counter.count_bulk_as_actions = count_bulk_as_actions;
+            counter
+        }
+
         /// Constructor that initializes counter to zero
         #[ink(constructor)]
         pub fn default() -> Self {
@@ -77,11 +176,50 @@ mod counter {
         /// Increment the counter by 1
         #[ink(message)]
         pub fn increment(&mut self) -> Result<()> {
-            self.value = self.value.checked_add(1).ok_or(Error::Overflow)?;
+            if self.paused {
+                return Err(Error::Paused);
+            }
+            let caller = self.env().caller();
+            self.ensure_cooldown_elapsed(caller)?;
+
+            self.value = self.state().increment()?;
+
+            self.last_action
+                .insert(caller, &self.env().block_timestamp());
+            self.record_increment(caller, 1);
+
+            self.env().emit_event(Incremented {
+                by: caller,
+                value: self.value,
+            });
+
+            Ok(())
+        }
 
+        /// Increment the counter by a custom amount
+        /// Counts as a single increment for the caller, or as `amount`
+        /// increments with `count_bulk_as_actions`
+        #[ink(message)]
+        pub fn increment_by(&mut self, amount: i32) -> Result<()> {
+            if self.paused {
+                return Err(Error::Paused);
+            }
+            if amount < 0 {
+                return Err(Error::InvalidAmount);
+            }
             let caller = self.env().caller();
-            let count = self.user_increments.get(caller).unwrap_or(0);
-            self.user_increments.insert(caller, &(count + 1));
+            self.ensure_cooldown_elapsed(caller)?;
+
+            self.value = self.state().increment_by(amount)?;
+
+            self.last_action
+                .insert(caller, &self.env().block_timestamp());
+            let actions = if self.count_bulk_as_actions {
+                amount.unsigned_abs()
+            } else {
+                1
+            };
+            self.record_increment(caller, actions);
 
             self.env().emit_event(Incremented {
                 by: caller,
@@ -94,9 +232,15 @@ mod counter {
         /// Decrement the counter by 1
         #[ink(message)]
         pub fn decrement(&mut self) -> Result<()> {
-            self.value = self.value.checked_sub(1).ok_or(Error::Underflow)?;
+            if self.paused {
+                return Err(Error::Paused);
+            }
+            self.value = self.state().decrement()?;
 
             let caller = self.env().caller();
+            let count = self.user_decrements.get(caller).unwrap_or(0);
+            self.user_decrements.insert(caller, &(count + 1));
+
             self.env().emit_event(Decremented {
                 by: caller,
                 value: self.value,
@@ -125,17 +269,190 @@ mod counter {
             Ok(())
         }
 
+        /// Set the counter to an arbitrary value (owner only)
+        #[ink(message)]
+        pub fn set_value(&mut self, value: i32) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::Unauthorized);
+            }
+            self.value = self.state().set(value)?;
+            self.env().emit_event(ValueSet { by: caller, value });
+
+            Ok(())
+        }
+
+        /// Get the contract version
+        #[ink(message)]
+        pub fn version(&self) -> String {
+            String::from(VERSION)
+        }
+
         /// Get the contract owner
         #[ink(message)]
         pub fn get_owner(&self) -> AccountId {
             self.owner
         }
 
+        /// Transfer ownership to a new account (owner only)
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::Unauthorized);
+            }
+            if new_owner == AccountId::from([0u8; 32]) {
+                return Err(Error::ZeroAddress);
+            }
+
+            self.owner = new_owner;
+            self.env().emit_event(OwnershipTransferred {
+                old_owner: caller,
+                new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Pause increments and decrements (owner only)
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::Unauthorized);
+            }
+
+            self.paused = true;
+            self.env().emit_event(Paused { by: caller });
+
+            Ok(())
+        }
+
+        /// Resume increments and decrements (owner only)
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::Unauthorized);
+            }
+
+            self.paused = false;
+            self.env().emit_event(Unpaused { by: caller });
+
+            Ok(())
+        }
+
+        /// Check whether the contract is paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Set or clear the maximum counter value (owner only)
+        #[ink(message)]
+        pub fn set_max_value(&mut self, max_value: Option<i32>) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::Unauthorized);
+            }
+
+            self.max_value = max_value;
+
+            Ok(())
+        }
+
+        /// Get the maximum counter value, if any
+        #[ink(message)]
+        pub fn get_max_value(&self) -> Option<i32> {
+            self.max_value
+        }
+
+        /// Set the per-user cooldown between increments (owner only)
+        #[ink(message)]
+        pub fn set_cooldown(&mut self, cooldown_ms: u64) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::Unauthorized);
+            }
+
+            self.cooldown_ms = cooldown_ms;
+
+            Ok(())
+        }
+
+        /// Get the per-user cooldown in milliseconds
+        #[ink(message)]
+        pub fn get_cooldown(&self) -> u64 {
+            self.cooldown_ms
+        }
+
+        /// Whether `increment_by` counts as `amount` increments in user stats
+        #[ink(message)]
+        pub fn count_bulk_as_actions(&self) -> bool {
+            self.count_bulk_as_actions
+        }
+
         /// Get how many times a user has incremented
         #[ink(message)]
         pub fn get_user_increments(&self, user: AccountId) -> u32 {
             self.user_increments.get(user).unwrap_or(0)
         }
+
+        /// Get how many times a user has decremented
+        #[ink(message)]
+        pub fn get_user_decrements(&self, user: AccountId) -> u32 {
+            self.user_decrements.get(user).unwrap_or(0)
+        }
+
+        /// Get how many distinct accounts have incremented
+        #[ink(message)]
+        pub fn get_user_count(&self) -> u32 {
+            self.user_count
+        }
+
+        /// Get a page of users' increment counts, in order of first increment,
+        /// starting at index `from` and returning at most `limit` entries
+        /// (capped at `MAX_USERS_PAGE`). Intended for front-end leaderboards
+        #[ink(message)]
+        pub fn get_all_user_increments(&self, from: u32, limit: u32) -> Vec<(AccountId, u32)> {
+            let end = from
+                .saturating_add(limit.min(MAX_USERS_PAGE))
+                .min(self.user_count);
+            (from..end)
+                .filter_map(|index| self.users.get(index))
+                .map(|user| (user, self.user_increments.get(user).unwrap_or(0)))
+                .collect()
+        }
+
+        /// Add `actions` to the caller's increment count, registering first-time users
+        fn record_increment(&mut self, caller: AccountId, actions: u32) {
+            let count = match self.user_increments.get(caller) {
+                Some(count) => count,
+                None => {
+                    self.users.insert(self.user_count, &caller);
+                    self.user_count = self.user_count.saturating_add(1);
+                    0
+                }
+            };
+            self.user_increments
+                .insert(caller, &count.saturating_add(actions));
+        }
+
+        /// Check that the caller's cooldown has elapsed since their last increment
+        fn ensure_cooldown_elapsed(&self, caller: AccountId) -> Result<()> {
+            if self.cooldown_ms == 0 {
+                return Ok(());
+            }
+            if let Some(last) = self.last_action.get(caller) {
+                if self.env().block_timestamp() < last.saturating_add(self.cooldown_ms) {
+                    return Err(Error::CooldownActive);
+                }
+            }
+            Ok(())
+        }
+
+        /// Snapshot of the value and cap for the shared counter rules
+        fn state(&self) -> CounterState<i32> {
+            CounterState::with_bounds(self.value, None, self.max_value)
+        }
     }
 
     #[cfg(test)]
@@ -186,5 +503,271 @@ mod counter {
             let mut counter = Counter::new(i32::MAX);
             assert_eq!(counter.increment(), Err(Error::Overflow));
         }
+
+        #[ink::test]
+        fn increment_by_works() {
+            let mut counter = Counter::new(10);
+            assert!(counter.increment_by(5).is_ok());
+            assert_eq!(counter.get(), 15);
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(counter.get_user_increments(accounts.alice), 1);
+        }
+
+        #[ink::test]
+        fn increment_by_overflow_fails() {
+            let mut counter = Counter::new(i32::MAX - 1);
+            assert_eq!(counter.increment_by(2), Err(Error::Overflow));
+            assert_eq!(counter.get(), i32::MAX - 1);
+        }
+
+        #[ink::test]
+        fn increment_by_negative_fails() {
+            let mut counter = Counter::new(10);
+            assert_eq!(counter.increment_by(-1), Err(Error::InvalidAmount));
+            assert_eq!(counter.get(), 10);
+        }
+
+        #[ink::test]
+        fn transfer_ownership_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut counter = Counter::new(42);
+
+            assert!(counter.transfer_ownership(accounts.bob).is_ok());
+            assert_eq!(counter.get_owner(), accounts.bob);
+
+            // Old owner can no longer reset
+            assert_eq!(counter.reset(), Err(Error::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(counter.reset().is_ok());
+            assert_eq!(counter.get(), 0);
+        }
+
+        #[ink::test]
+        fn transfer_ownership_not_owner_fails() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut counter = Counter::new(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                counter.transfer_ownership(accounts.bob),
+                Err(Error::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_ownership_to_zero_fails() {
+            let mut counter = Counter::new(0);
+            assert_eq!(
+                counter.transfer_ownership(AccountId::from([0u8; 32])),
+                Err(Error::ZeroAddress)
+            );
+        }
+
+        #[ink::test]
+        fn increment_fails_while_paused() {
+            let mut counter = Counter::new(0);
+            assert!(counter.pause().is_ok());
+            assert!(counter.is_paused());
+
+            assert_eq!(counter.increment(), Err(Error::Paused));
+            assert_eq!(counter.increment_by(5), Err(Error::Paused));
+            assert_eq!(counter.decrement(), Err(Error::Paused));
+            assert_eq!(counter.get(), 0);
+
+            // Reset stays available to the owner
+            assert!(counter.reset().is_ok());
+
+            assert!(counter.unpause().is_ok());
+            assert!(!counter.is_paused());
+            assert!(counter.increment().is_ok());
+            assert_eq!(counter.get(), 1);
+        }
+
+        #[ink::test]
+        fn pause_not_owner_fails() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut counter = Counter::new(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(counter.pause(), Err(Error::Unauthorized));
+            assert_eq!(counter.unpause(), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn increment_up_to_cap_works() {
+            let mut counter = Counter::new_capped(8, Some(10));
+            assert!(counter.increment().is_ok());
+            assert!(counter.increment_by(1).is_ok());
+            assert_eq!(counter.get(), 10);
+        }
+
+        #[ink::test]
+        fn increment_past_cap_fails() {
+            let mut counter = Counter::new_capped(10, Some(10));
+            assert_eq!(counter.increment(), Err(Error::CapExceeded));
+            assert_eq!(counter.increment_by(3), Err(Error::CapExceeded));
+            assert_eq!(counter.get(), 10);
+        }
+
+        #[ink::test]
+        fn set_max_value_works() {
+            let mut counter = Counter::new(5);
+            assert_eq!(counter.get_max_value(), None);
+            assert!(counter.increment_by(100).is_ok());
+
+            assert!(counter.set_max_value(Some(106)).is_ok());
+            assert_eq!(counter.get_max_value(), Some(106));
+            assert!(counter.increment().is_ok());
+            assert_eq!(counter.increment(), Err(Error::CapExceeded));
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(counter.set_max_value(None), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn set_value_works() {
+            let mut counter = Counter::new(0);
+            assert!(counter.set_value(-1234).is_ok());
+            assert_eq!(counter.get(), -1234);
+        }
+
+        #[ink::test]
+        fn set_value_not_owner_fails() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut counter = Counter::new(7);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(counter.set_value(100), Err(Error::Unauthorized));
+            assert_eq!(counter.get(), 7);
+        }
+
+        #[ink::test]
+        fn set_value_respects_cap() {
+            let mut counter = Counter::new_capped(0, Some(50));
+            assert!(counter.set_value(50).is_ok());
+            assert_eq!(counter.set_value(51), Err(Error::CapExceeded));
+            assert_eq!(counter.get(), 50);
+        }
+
+        #[ink::test]
+        fn user_decrements_tracked() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut counter = Counter::new(10);
+
+            assert!(counter.increment().is_ok());
+            assert!(counter.decrement().is_ok());
+            assert!(counter.decrement().is_ok());
+
+            assert_eq!(counter.get_user_decrements(accounts.alice), 2);
+            assert_eq!(counter.get_user_increments(accounts.alice), 1);
+            assert_eq!(counter.get_user_decrements(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn cooldown_blocks_rapid_increments() {
+            let mut counter = Counter::new(0);
+            assert!(counter.set_cooldown(1_000).is_ok());
+            assert_eq!(counter.get_cooldown(), 1_000);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5_000);
+            assert!(counter.increment().is_ok());
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5_999);
+            assert_eq!(counter.increment(), Err(Error::CooldownActive));
+            assert_eq!(counter.increment_by(2), Err(Error::CooldownActive));
+            assert_eq!(counter.get(), 1);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(6_000);
+            assert!(counter.increment_by(2).is_ok());
+            assert_eq!(counter.get(), 3);
+        }
+
+        #[ink::test]
+        fn zero_cooldown_disables_check() {
+            let mut counter = Counter::new(0);
+            assert!(counter.increment().is_ok());
+            assert!(counter.increment().is_ok());
+            assert_eq!(counter.get(), 2);
+        }
+
+        #[ink::test]
+        fn all_user_increments_lists_each_user_once() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut counter = Counter::new(0);
+
+            assert!(counter.increment().is_ok());
+            assert!(counter.increment_by(5).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(counter.increment().is_ok());
+            assert!(counter.decrement().is_ok());
+
+            assert_eq!(counter.get_user_count(), 2);
+            assert_eq!(
+                counter.get_all_user_increments(0, 10),
+                vec![(accounts.alice, 2), (accounts.bob, 1)]
+            );
+        }
+
+        #[ink::test]
+        fn all_user_increments_empty_initially() {
+            let mut counter = Counter::new(0);
+            assert!(counter.decrement().is_ok());
+            assert_eq!(counter.get_user_count(), 0);
+            assert!(counter.get_all_user_increments(0, 10).is_empty());
+        }
+
+        #[ink::test]
+        fn all_user_increments_paginates() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut counter = Counter::new(0);
+            for caller in [accounts.alice, accounts.bob, accounts.charlie] {
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                assert!(counter.increment().is_ok());
+            }
+
+            assert_eq!(
+                counter.get_all_user_increments(0, 2),
+                vec![(accounts.alice, 1), (accounts.bob, 1)]
+            );
+            assert_eq!(
+                counter.get_all_user_increments(2, 2),
+                vec![(accounts.charlie, 1)]
+            );
+            assert!(counter.get_all_user_increments(3, 2).is_empty());
+            assert_eq!(counter.get_all_user_increments(0, u32::MAX).len(), 3);
+        }
+
+        #[ink::test]
+        fn version_matches_crate() {
+            let counter = Counter::default();
+            assert_eq!(counter.version(), env!("CARGO_PKG_VERSION"));
+        }
+
+        #[ink::test]
+        fn bulk_increment_counts_as_one_action_by_default() {
+            let mut counter = Counter::new_with_bulk_counting(0, false);
+            assert!(!counter.count_bulk_as_actions());
+            assert!(counter.increment_by(5).is_ok());
+            assert!(counter.increment().is_ok());
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(counter.get_user_increments(accounts.alice), 2);
+        }
+
+        #[ink::test]
+        fn bulk_increment_counts_units_when_enabled() {
+            let mut counter = Counter::new_with_bulk_counting(0, true);
+            assert!(counter.count_bulk_as_actions());
+            assert!(counter.increment_by(5).is_ok());
+            assert!(counter.increment().is_ok());
+            assert_eq!(counter.get(), 6);
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(counter.get_user_increments(accounts.alice), 6);
+        }
     }
 }
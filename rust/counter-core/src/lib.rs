@@ -0,0 +1,202 @@
+#![cfg_attr(not(test), no_std)]
+
+//! Counter Core - checked counter arithmetic shared across chains
+//!
+//! The NEAR, Solana and ink! counters store different integer types
+//! (`i64`, `u64`, `i32`), but the rules for moving the value are the same:
+//! every change is checked for overflow/underflow and must stay inside the
+//! configured bounds. `CounterState` captures those rules once so each
+//! contract only maps `CounterError` onto its own error type.
+
+/// Integer types a counter can hold
+pub trait CounterValue: Copy + PartialOrd {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_counter_value {
+    ($($t:ty),*) => {
+        $(
+            impl CounterValue for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_counter_value!(i32, i64, i128, u32, u64, u128);
+
+/// Errors shared by every counter implementation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterError {
+    /// Arithmetic overflow
+    Overflow,
+    /// Arithmetic underflow
+    Underflow,
+    /// Value would drop below the minimum bound
+    BelowMin,
+    /// Value would exceed the maximum bound
+    AboveMax,
+}
+
+impl CounterError {
+    /// Human-readable message, used as the panic message on NEAR
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CounterError::Overflow => "Overflow error",
+            CounterError::Underflow => "Underflow error",
+            CounterError::BelowMin => "value below min bound",
+            CounterError::AboveMax => "value exceeds max bound",
+        }
+    }
+}
+
+/// A counter value together with its optional inclusive bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterState<T> {
+    value: T,
+    min: Option<T>,
+    max: Option<T>,
+}
+
+impl<T: CounterValue> CounterState<T> {
+    /// Unbounded counter starting at `value`
+    pub fn new(value: T) -> Self {
+        Self::with_bounds(value, None, None)
+    }
+
+    /// Counter starting at `value`, constrained to `[min, max]`
+    pub fn with_bounds(value: T, min: Option<T>, max: Option<T>) -> Self {
+        Self { value, min, max }
+    }
+
+    /// Current value
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Check a prospective value against the bounds
+    pub fn check_bounds(&self, value: T) -> Result<T, CounterError> {
+        if let Some(min) = self.min {
+            if value < min {
+                return Err(CounterError::BelowMin);
+            }
+        }
+        if let Some(max) = self.max {
+            if value > max {
+                return Err(CounterError::AboveMax);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Add one
+    pub fn increment(&mut self) -> Result<T, CounterError> {
+        self.increment_by(T::ONE)
+    }
+
+    /// Subtract one
+    pub fn decrement(&mut self) -> Result<T, CounterError> {
+        self.decrement_by(T::ONE)
+    }
+
+    /// Add `amount`, leaving the state untouched on error
+    pub fn increment_by(&mut self, amount: T) -> Result<T, CounterError> {
+        let value = self
+            .value
+            .checked_add(amount)
+            .ok_or(CounterError::Overflow)?;
+        self.set(value)
+    }
+
+    /// Subtract `amount`, leaving the state untouched on error
+    pub fn decrement_by(&mut self, amount: T) -> Result<T, CounterError> {
+        let value = self
+            .value
+            .checked_sub(amount)
+            .ok_or(CounterError::Underflow)?;
+        self.set(value)
+    }
+
+    /// Replace the value, subject to the bounds
+    pub fn set(&mut self, value: T) -> Result<T, CounterError> {
+        self.value = self.check_bounds(value)?;
+        Ok(self.value)
+    }
+
+    /// Return to zero, ignoring bounds so a counter can always be recovered
+    pub fn reset(&mut self) -> T {
+        self.value = T::ZERO;
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment_and_decrement_work() {
+        let mut state = CounterState::new(10i64);
+        assert_eq!(state.increment(), Ok(11));
+        assert_eq!(state.decrement_by(5), Ok(6));
+        assert_eq!(state.increment_by(4), Ok(10));
+        assert_eq!(state.decrement(), Ok(9));
+        assert_eq!(state.value(), 9);
+    }
+
+    #[test]
+    fn overflow_is_rejected() {
+        let mut state = CounterState::new(i32::MAX - 1);
+        assert_eq!(state.increment_by(2), Err(CounterError::Overflow));
+        assert_eq!(state.value(), i32::MAX - 1);
+        assert_eq!(state.increment(), Ok(i32::MAX));
+        assert_eq!(state.increment(), Err(CounterError::Overflow));
+    }
+
+    #[test]
+    fn underflow_is_rejected() {
+        let mut state = CounterState::new(0u64);
+        assert_eq!(state.decrement(), Err(CounterError::Underflow));
+
+        let mut signed = CounterState::new(i64::MIN);
+        assert_eq!(signed.decrement(), Err(CounterError::Underflow));
+        assert_eq!(signed.value(), i64::MIN);
+    }
+
+    #[test]
+    fn bounds_are_inclusive() {
+        let mut state = CounterState::with_bounds(5u64, Some(5), Some(10));
+        assert_eq!(state.increment_by(5), Ok(10));
+        assert_eq!(state.increment(), Err(CounterError::AboveMax));
+        assert_eq!(state.set(5), Ok(5));
+        assert_eq!(state.decrement(), Err(CounterError::BelowMin));
+        assert_eq!(state.set(11), Err(CounterError::AboveMax));
+        assert_eq!(state.value(), 5);
+    }
+
+    #[test]
+    fn reset_ignores_bounds() {
+        let mut state = CounterState::with_bounds(7i32, Some(5), None);
+        assert_eq!(state.reset(), 0);
+        assert_eq!(state.value(), 0);
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(CounterError::Overflow.as_str(), "Overflow error");
+        assert_eq!(CounterError::AboveMax.as_str(), "value exceeds max bound");
+    }
+}
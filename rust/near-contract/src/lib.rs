@@ -1,6 +1,43 @@
+use counter_core::CounterState;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LookupMap, Vector};
-use near_sdk::{env, near_bindgen, AccountId, PanicOnDefault};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedSet, Vector};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json;
+use near_sdk::{
+    env, ext_contract, near_bindgen, AccountId, Gas, NearToken, PanicOnDefault, Promise,
+    PromiseError,
+};
+
+/// Semantic version of this contract build, taken from the crate version
+/// Bump it whenever the storage layout changes (and `migrate` with it)
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// NEP-297 standard name used in emitted events
+const EVENT_STANDARD: &str = "counter";
+/// NEP-297 standard version used in emitted events
+const EVENT_STANDARD_VERSION: &str = "1.0.0";
+/// Default capacity of the event log
+const DEFAULT_MAX_EVENTS: u64 = 1_000;
+/// Largest window accepted by `get_recent_events_n`
+const MAX_RECENT_EVENTS: u64 = 1_000;
+/// Largest page accepted by `get_events_by_kind`
+const MAX_EVENTS_BY_KIND: u64 = 100;
+/// Largest account list accepted by `get_user_increments_batch`
+const MAX_BATCH_ACCOUNTS: usize = 100;
+/// Default capacity of the value history
+const DEFAULT_MAX_HISTORY: u64 = 1_000;
+/// Largest repetition count accepted by `increment_many`
+const MAX_BATCH_INCREMENTS: u32 = 10_000;
+/// Gas attached to the remote `increment` call
+const GAS_FOR_REMOTE_INCREMENT: Gas = Gas::from_tgas(10);
+/// Gas reserved for the `on_increment_remote_complete` callback
+const GAS_FOR_REMOTE_INCREMENT_CALLBACK: Gas = Gas::from_tgas(5);
+
+/// Interface of a counter deployed on another account
+#[ext_contract(ext_counter)]
+pub trait ExtCounter {
+    fn increment(&mut self) -> i64;
+}
 
 /// NEAR Protocol Smart Contract
 /// A counter contract with owner management and event logging
@@ -12,29 +49,509 @@ pub struct Counter {
     value: i64,
     /// Contract owner
     owner: AccountId,
+    /// Account proposed as the next owner, awaiting acceptance
+    pending_owner: Option<AccountId>,
+    /// Accounts allowed to reset, set and clear events alongside the owner
+    admins: UnorderedSet<AccountId>,
+    /// Amount applied by `increment` and `decrement`
+    step: i64,
+    /// Behavior on arithmetic overflow
+    overflow_mode: OverflowMode,
+    /// Optional lower bound on the counter value
+    min_bound: Option<i64>,
+    /// Optional upper bound on the counter value
+    max_bound: Option<i64>,
+    /// Value applied by `reset`
+    reset_value: i64,
+    /// Whether mutations are currently frozen
+    paused: bool,
+    /// Whether `decrement`/`decrement_by` are restricted to the owner
+    decrement_owner_only: bool,
+    /// Delta applied by the last increment/decrement, for `undo`
+    last_delta: Option<i64>,
+    /// Value before the last `set_counter`/`reset`, for `undo`
+    last_was_set: Option<i64>,
     /// Total number of increments
     total_increments: u64,
+    /// Total number of decrements
+    total_decrements: u64,
     /// Track per-user increments
     user_increments: LookupMap<AccountId, u64>,
-    /// Event log
+    /// Track per-user decrements
+    user_decrements: LookupMap<AccountId, u64>,
+    /// Account with the most increments and its count, kept up to date so it
+    /// can be read without enumerating `user_increments`
+    top_contributor: Option<(AccountId, u64)>,
+    /// Distinct accounts that have incremented or decremented
+    participants: Vector<AccountId>,
+    /// Membership index for `participants`
+    participant_set: LookupSet<AccountId>,
+    /// Minimum interval between a user's increments in nanoseconds (0 disables)
+    cooldown_ns: u64,
+    /// Deposit `increment_paid` requires, in yoctoNEAR
+    min_increment_deposit: u128,
+    /// Block timestamp of each user's last increment
+    last_action: LookupMap<AccountId, u64>,
+    /// Whether only whitelisted accounts (and the owner) may mutate the counter
+    whitelist_enabled: bool,
+    /// Accounts allowed to mutate the counter when the whitelist is enabled
+    whitelist: LookupMap<AccountId, bool>,
+    /// Independent named counters living alongside the main value
+    counters: LookupMap<String, i64>,
+    /// Event log of NEP-297 event JSON (without the `EVENT_JSON:` prefix)
+    ///
+    /// Stored as a ring buffer: the vector grows until it holds `max_events`
+    /// entries, after which each new event overwrites the oldest slot at
+    /// `event_head` and the head advances. Logical index `i` (oldest first)
+    /// lives at physical slot `(event_head + i) % len`.
+    event_log: Vector<String>,
+    /// Physical slot of the oldest event once the log has wrapped
+    event_head: u64,
+    /// Maximum number of events retained in the log
+    max_events: u64,
+    /// Whether events are stored in `event_log` as well as logged
+    persist_events: bool,
+    /// `(block_timestamp, value)` after each mutation of the main value,
+    /// stored as a ring buffer like `event_log`
+    history: Vector<(u64, i64)>,
+    /// Physical slot of the oldest history entry once the buffer has wrapped
+    history_head: u64,
+    /// Maximum number of history entries retained
+    max_history: u64,
+    /// Co-owners who confirm `reset`/`set_counter` proposals once multisig is on
+    owners: UnorderedSet<AccountId>,
+    /// Distinct confirmations needed to execute a proposal (0 disables multisig)
+    threshold: u8,
+    /// Privileged actions awaiting confirmation, by id
+    proposals: LookupMap<u64, Proposal>,
+    /// Id assigned to the next proposal
+    next_proposal_id: u64,
+}
+
+/// How `increment`/`decrement` behave when the result leaves the `i64` range
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum OverflowMode {
+    /// Abort the transaction with "Overflow error" / "Underflow error"
+    Panic,
+    /// Clamp the value at `i64::MAX` / `i64::MIN`
+    Saturate,
+}
+
+/// Privileged action that needs `threshold` owner confirmations under multisig
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ActionKind {
+    /// `reset`; the proposal value is ignored
+    Reset,
+    /// `set_counter` to the proposal value
+    SetCounter,
+    /// Replace the co-owners with the proposal owners and the threshold with
+    /// the proposal value; created through `propose_set_multisig`
+    SetMultisig,
+}
+
+/// Pending multisig action returned by `get_proposal`
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Proposal {
+    pub kind: ActionKind,
+    pub value: i64,
+    /// New co-owners for `SetMultisig`, empty otherwise
+    pub owners: Vec<AccountId>,
+    pub confirmations: Vec<AccountId>,
+    pub executed: bool,
+}
+
+/// Events emitted by the counter
+/// Serialized as the `data` entry of a NEP-297 event named by `CounterEvent::name`
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde", untagged)]
+pub enum CounterEvent {
+    Increment {
+        by: AccountId,
+        step: i64,
+        value: i64,
+        saturated: bool,
+    },
+    IncrementMany {
+        by: AccountId,
+        times: u32,
+        step: i64,
+        value: i64,
+        saturated: bool,
+    },
+    Decrement {
+        by: AccountId,
+        step: i64,
+        value: i64,
+        saturated: bool,
+    },
+    IncrementBy {
+        by: AccountId,
+        amount: i64,
+        value: i64,
+        saturated: bool,
+    },
+    DecrementBy {
+        by: AccountId,
+        amount: i64,
+        value: i64,
+        saturated: bool,
+    },
+    MultiplyBy {
+        by: AccountId,
+        factor: i64,
+        value: i64,
+    },
+    DivideBy {
+        by: AccountId,
+        divisor: i64,
+        value: i64,
+    },
+    Reset {
+        by: AccountId,
+        reset_value: i64,
+    },
+    SetResetValue {
+        by: AccountId,
+        reset_value: i64,
+    },
+    SetCounter {
+        by: AccountId,
+        value: i64,
+    },
+    Undo {
+        by: AccountId,
+        value: i64,
+    },
+    Paused {
+        by: AccountId,
+    },
+    Unpaused {
+        by: AccountId,
+    },
+    SetCooldown {
+        by: AccountId,
+        cooldown_ns: u64,
+    },
+    SetMinIncrementDeposit {
+        by: AccountId,
+        amount: U128,
+    },
+    SetDecrementOwnerOnly {
+        by: AccountId,
+        enabled: bool,
+    },
+    SetPersistEvents {
+        by: AccountId,
+        enabled: bool,
+    },
+    SetStep {
+        by: AccountId,
+        step: i64,
+    },
+    SetOverflowMode {
+        by: AccountId,
+        mode: OverflowMode,
+    },
+    SetBounds {
+        by: AccountId,
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+    AddAdmin {
+        by: AccountId,
+        account_id: AccountId,
+    },
+    RemoveAdmin {
+        by: AccountId,
+        account_id: AccountId,
+    },
+    AddToWhitelist {
+        by: AccountId,
+        account_id: AccountId,
+    },
+    RemoveFromWhitelist {
+        by: AccountId,
+        account_id: AccountId,
+    },
+    SetWhitelistEnabled {
+        by: AccountId,
+        enabled: bool,
+    },
+    OwnerProposed {
+        by: AccountId,
+        new_owner: AccountId,
+    },
+    OwnerAccepted {
+        by: AccountId,
+        previous_owner: AccountId,
+    },
+    CreateCounter {
+        by: AccountId,
+        name: String,
+    },
+    IncrementNamed {
+        by: AccountId,
+        name: String,
+        value: i64,
+    },
+    DecrementNamed {
+        by: AccountId,
+        name: String,
+        value: i64,
+    },
+    SetMultisig {
+        by: AccountId,
+        owners: Vec<AccountId>,
+        threshold: u8,
+    },
+    ActionProposed {
+        by: AccountId,
+        id: u64,
+        kind: ActionKind,
+        value: i64,
+    },
+    ActionConfirmed {
+        by: AccountId,
+        id: u64,
+        confirmations: u8,
+    },
+    Withdraw {
+        by: AccountId,
+        amount: U128,
+    },
+    IncrementRemote {
+        target: AccountId,
+        success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        value: Option<i64>,
+    },
+}
+
+impl CounterEvent {
+    /// NEP-297 event name
+    pub fn name(&self) -> &'static str {
+        match self {
+            CounterEvent::Increment { .. } => "increment",
+            CounterEvent::IncrementMany { .. } => "increment_many",
+            CounterEvent::Decrement { .. } => "decrement",
+            CounterEvent::IncrementBy { .. } => "increment_by",
+            CounterEvent::DecrementBy { .. } => "decrement_by",
+            CounterEvent::MultiplyBy { .. } => "multiply_by",
+            CounterEvent::DivideBy { .. } => "divide_by",
+            CounterEvent::Reset { .. } => "reset",
+            CounterEvent::SetResetValue { .. } => "set_reset_value",
+            CounterEvent::SetCounter { .. } => "set_counter",
+            CounterEvent::Undo { .. } => "undo",
+            CounterEvent::Paused { .. } => "paused",
+            CounterEvent::Unpaused { .. } => "unpaused",
+            CounterEvent::SetCooldown { .. } => "set_cooldown",
+            CounterEvent::SetMinIncrementDeposit { .. } => "set_min_increment_deposit",
+            CounterEvent::SetDecrementOwnerOnly { .. } => "set_decrement_owner_only",
+            CounterEvent::SetPersistEvents { .. } => "set_persist_events",
+            CounterEvent::SetStep { .. } => "set_step",
+            CounterEvent::SetOverflowMode { .. } => "set_overflow_mode",
+            CounterEvent::SetBounds { .. } => "set_bounds",
+            CounterEvent::AddAdmin { .. } => "add_admin",
+            CounterEvent::RemoveAdmin { .. } => "remove_admin",
+            CounterEvent::AddToWhitelist { .. } => "add_to_whitelist",
+            CounterEvent::RemoveFromWhitelist { .. } => "remove_from_whitelist",
+            CounterEvent::SetWhitelistEnabled { .. } => "set_whitelist_enabled",
+            CounterEvent::OwnerProposed { .. } => "owner_proposed",
+            CounterEvent::OwnerAccepted { .. } => "owner_accepted",
+            CounterEvent::CreateCounter { .. } => "create_counter",
+            CounterEvent::IncrementNamed { .. } => "increment_named",
+            CounterEvent::DecrementNamed { .. } => "decrement_named",
+            CounterEvent::SetMultisig { .. } => "set_multisig",
+            CounterEvent::ActionProposed { .. } => "action_proposed",
+            CounterEvent::ActionConfirmed { .. } => "action_confirmed",
+            CounterEvent::Withdraw { .. } => "withdraw",
+            CounterEvent::IncrementRemote { .. } => "increment_remote",
+        }
+    }
+}
+
+/// NEP-297 envelope: `{"standard", "version", "event", "data": [...]}`
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct Nep297Event<'a> {
+    standard: &'static str,
+    version: &'static str,
+    event: &'static str,
+    data: [EventData<'a>; 1],
+}
+
+/// Just the event name of a stored NEP-297 envelope
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct Nep297Kind {
+    event: String,
+}
+
+/// Event fields plus the block context they were emitted in
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+struct EventData<'a> {
+    #[serde(flatten)]
+    event: &'a CounterEvent,
+    block_height: u64,
+    timestamp: u64,
+}
+
+/// Storage layout of the counter as originally deployed (v0.1.0)
+/// Read by `migrate` when upgrading existing deployments
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldCounter {
+    value: i64,
+    owner: AccountId,
+    total_increments: u64,
+    user_increments: LookupMap<AccountId, u64>,
     event_log: Vector<String>,
 }
 
+/// Aggregate counter statistics returned by `get_stats`
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CounterStats {
+    pub value: i64,
+    pub total_increments: u64,
+    pub total_decrements: u64,
+}
+
 #[near_bindgen]
 impl Counter {
     /// Initialize the contract
     #[init]
     pub fn new(initial_value: i64) -> Self {
+        Self::new_with_step(initial_value, 1)
+    }
+
+    /// Initialize the contract with a custom step size
+    #[init]
+    pub fn new_with_step(initial_value: i64, step: i64) -> Self {
         assert!(!env::state_exists(), "Already initialized");
+        assert!(step > 0, "step must be positive");
         Self {
             value: initial_value,
             owner: env::predecessor_account_id(),
+            pending_owner: None,
+            admins: UnorderedSet::new(b"a"),
+            step,
+            overflow_mode: OverflowMode::Panic,
+            min_bound: None,
+            max_bound: None,
+            reset_value: 0,
+            paused: false,
+            decrement_owner_only: false,
+            last_delta: None,
+            last_was_set: None,
             total_increments: 0,
+            total_decrements: 0,
             user_increments: LookupMap::new(b"u"),
+            user_decrements: LookupMap::new(b"d"),
+            top_contributor: None,
+            participants: Vector::new(b"p"),
+            participant_set: LookupSet::new(b"s"),
+            cooldown_ns: 0,
+            min_increment_deposit: 0,
+            last_action: LookupMap::new(b"l"),
+            whitelist_enabled: false,
+            whitelist: LookupMap::new(b"w"),
+            counters: LookupMap::new(b"n"),
             event_log: Vector::new(b"e"),
+            event_head: 0,
+            max_events: DEFAULT_MAX_EVENTS,
+            persist_events: true,
+            history: Vector::new(b"h"),
+            history_head: 0,
+            max_history: DEFAULT_MAX_HISTORY,
+            owners: UnorderedSet::new(b"o"),
+            threshold: 0,
+            proposals: LookupMap::new(b"q"),
+            next_proposal_id: 0,
+        }
+    }
+
+    /// Migrate state written by the original (v0.1.0) layout
+    ///
+    /// Upgrade procedure: `near deploy` the new WASM over the existing
+    /// account, then call `migrate` from the contract account itself.
+    /// Existing value, owner, increment counts and events are preserved;
+    /// new fields start at their defaults. The participant list and the top
+    /// contributor start empty until the owner calls `seed_participants` and
+    /// `seed_top_contributor` with the pre-upgrade incrementers.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: OldCounter = env::state_read().expect("Failed to read old state");
+        let max_events = DEFAULT_MAX_EVENTS.max(old.event_log.len());
+        Self {
+            value: old.value,
+            owner: old.owner,
+            pending_owner: None,
+            admins: UnorderedSet::new(b"a"),
+            step: 1,
+            overflow_mode: OverflowMode::Panic,
+            min_bound: None,
+            max_bound: None,
+            reset_value: 0,
+            paused: false,
+            decrement_owner_only: false,
+            last_delta: None,
+            last_was_set: None,
+            total_increments: old.total_increments,
+            total_decrements: 0,
+            user_increments: old.user_increments,
+            user_decrements: LookupMap::new(b"d"),
+            top_contributor: None,
+            participants: Vector::new(b"p"),
+            participant_set: LookupSet::new(b"s"),
+            cooldown_ns: 0,
+            min_increment_deposit: 0,
+            last_action: LookupMap::new(b"l"),
+            whitelist_enabled: false,
+            whitelist: LookupMap::new(b"w"),
+            counters: LookupMap::new(b"n"),
+            event_log: old.event_log,
+            event_head: 0,
+            max_events,
+            persist_events: true,
+            history: Vector::new(b"h"),
+            history_head: 0,
+            max_history: DEFAULT_MAX_HISTORY,
+            owners: UnorderedSet::new(b"o"),
+            threshold: 0,
+            proposals: LookupMap::new(b"q"),
+            next_proposal_id: 0,
         }
     }
 
+    /// Get the deployed contract version
+    pub fn version(&self) -> String {
+        VERSION.to_string()
+    }
+
+    /// Get the co-owners confirming multisig proposals
+    pub fn get_owners(&self) -> Vec<AccountId> {
+        self.owners.to_vec()
+    }
+
+    /// Get the confirmations needed per proposal (0 when multisig is off)
+    pub fn get_threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Get a multisig proposal by id
+    pub fn get_proposal(&self, id: u64) -> Option<Proposal> {
+        self.proposals.get(&id)
+    }
+
     /// Get current counter value
     pub fn get_counter(&self) -> i64 {
         self.value
@@ -45,192 +562,3181 @@ impl Counter {
         self.owner.clone()
     }
 
+    /// Get the account proposed as next owner, if any
+    pub fn get_pending_owner(&self) -> Option<AccountId> {
+        self.pending_owner.clone()
+    }
+
+    /// Check whether an account is an admin
+    pub fn is_admin(&self, account_id: AccountId) -> bool {
+        self.admins.contains(&account_id)
+    }
+
+    /// Check whether the whitelist is enforced
+    pub fn is_whitelist_enabled(&self) -> bool {
+        self.whitelist_enabled
+    }
+
+    /// Check whether an account is on the whitelist
+    pub fn is_whitelisted(&self, account_id: AccountId) -> bool {
+        self.whitelist.get(&account_id).unwrap_or(false)
+    }
+
+    /// Get the step applied by `increment` and `decrement`
+    pub fn get_step(&self) -> i64 {
+        self.step
+    }
+
+    /// Get the overflow behavior
+    pub fn get_overflow_mode(&self) -> OverflowMode {
+        self.overflow_mode
+    }
+
+    /// Get configured (min, max) bounds
+    pub fn get_bounds(&self) -> (Option<i64>, Option<i64>) {
+        (self.min_bound, self.max_bound)
+    }
+
+    /// Get the value applied by `reset`
+    pub fn get_reset_value(&self) -> i64 {
+        self.reset_value
+    }
+
+    /// Check whether mutations are paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
     /// Get total increments
     pub fn get_total_increments(&self) -> u64 {
         self.total_increments
     }
 
+    /// Get total decrements
+    pub fn get_total_decrements(&self) -> u64 {
+        self.total_decrements
+    }
+
+    /// Get value and increment/decrement totals in a single call
+    pub fn get_stats(&self) -> CounterStats {
+        CounterStats {
+            value: self.value,
+            total_increments: self.total_increments,
+            total_decrements: self.total_decrements,
+        }
+    }
+
     /// Get user-specific increments
     pub fn get_user_increments(&self, account_id: AccountId) -> u64 {
         self.user_increments.get(&account_id).unwrap_or(0)
     }
 
-    /// Increment counter by 1
-    pub fn increment(&mut self) {
-        self.value = self.value.checked_add(1).expect("Overflow error");
+    /// Get increments for several accounts in the order given (0 for unknown accounts)
+    pub fn get_user_increments_batch(&self, accounts: Vec<AccountId>) -> Vec<u64> {
+        assert!(accounts.len() <= MAX_BATCH_ACCOUNTS, "too many accounts");
+        accounts
+            .iter()
+            .map(|account_id| self.user_increments.get(account_id).unwrap_or(0))
+            .collect()
+    }
+
+    /// Get the account with the most increments and its count, if anyone has incremented
+    /// Ties keep the account that reached the count first
+    pub fn get_top_contributor(&self) -> Option<(AccountId, u64)> {
+        self.top_contributor.clone()
+    }
+
+    /// Get user-specific decrements
+    pub fn get_user_decrements(&self, account_id: AccountId) -> u64 {
+        self.user_decrements.get(&account_id).unwrap_or(0)
+    }
+
+    /// Get user activity as (increments, decrements)
+    pub fn get_user_activity(&self, account_id: AccountId) -> (u64, u64) {
+        (
+            self.user_increments.get(&account_id).unwrap_or(0),
+            self.user_decrements.get(&account_id).unwrap_or(0),
+        )
+    }
+
+    /// Get the number of distinct accounts that have incremented or decremented
+    pub fn get_participant_count(&self) -> u64 {
+        self.participants.len()
+    }
+
+    /// Get a page of participants in first-seen order
+    pub fn get_participants(&self, from_index: u64, limit: u64) -> Vec<AccountId> {
+        let len = self.participants.len();
+        let end = from_index.saturating_add(limit).min(len);
+        (from_index.min(end)..end)
+            .map(|i| self.participants.get(i).unwrap())
+            .collect()
+    }
+
+    /// Get the per-user increment cooldown in nanoseconds
+    pub fn get_cooldown(&self) -> u64 {
+        self.cooldown_ns
+    }
+
+    /// Get the deposit `increment_paid` requires, in yoctoNEAR
+    pub fn get_min_increment_deposit(&self) -> U128 {
+        U128(self.min_increment_deposit)
+    }
+
+    /// Whether decrements are restricted to the owner
+    pub fn is_decrement_owner_only(&self) -> bool {
+        self.decrement_owner_only
+    }
+
+    /// Increment counter by the configured step, returning the new value
+    pub fn increment(&mut self) -> i64 {
+        self.assert_not_paused();
+        self.assert_whitelisted();
+        let (value, saturated) = self.apply_add(self.step);
+        self.assert_within_bounds(value);
+        self.record_delta(value - self.value);
+        self.value = value;
+        self.record_history();
         self.total_increments += 1;
 
         let caller = env::predecessor_account_id();
-        let user_count = self.user_increments.get(&caller).unwrap_or(0);
-        self.user_increments.insert(&caller, &(user_count + 1));
+        self.enforce_cooldown(&caller);
+        self.record_user_increments(&caller, 1);
+
+        self.log_event(CounterEvent::Increment {
+            by: caller,
+            step: self.step,
+            value: self.value,
+            saturated,
+        });
+
+        self.value
+    }
+
+    /// Increment only if the value still equals `expected`, returning the new value
+    /// Lets clients do an optimistic read-modify-write
+    pub fn increment_if(&mut self, expected: i64) -> i64 {
+        assert_eq!(self.value, expected, "value changed");
+        self.increment()
+    }
 
-        // Log event
-        let event = format!(
-            "{{\"event\":\"increment\",\"by\":\"{}\",\"value\":{}}}",
-            caller, self.value
+    /// `increment` with an attached deposit of at least `min_increment_deposit`,
+    /// returning the new value. Any excess deposit is refunded to the caller
+    #[payable]
+    pub fn increment_paid(&mut self) -> i64 {
+        let deposit = env::attached_deposit().as_yoctonear();
+        assert!(
+            deposit >= self.min_increment_deposit,
+            "insufficient deposit"
         );
-        env::log_str(&event);
-        self.event_log.push(&event);
+        let value = self.increment();
+
+        let excess = deposit - self.min_increment_deposit;
+        if excess > 0 {
+            let _ = Promise::new(env::predecessor_account_id())
+                .transfer(NearToken::from_yoctonear(excess));
+        }
+
+        value
     }
 
-    /// Decrement counter by 1
-    pub fn decrement(&mut self) {
-        self.value = self.value.checked_sub(1).expect("Underflow error");
+    /// Apply `increment` `times` times in one call, returning the new value
+    /// Counts as `times` increments but logs a single summarizing event
+    pub fn increment_many(&mut self, times: u32) -> i64 {
+        assert!(times <= MAX_BATCH_INCREMENTS, "batch too large");
+        if times == 0 {
+            return self.value;
+        }
+        self.assert_not_paused();
+        self.assert_whitelisted();
+        let amount = self.step.checked_mul(times as i64).expect("Overflow error");
+        let (value, saturated) = self.apply_add(amount);
+        self.assert_within_bounds(value);
+        self.record_delta(value - self.value);
+        self.value = value;
+        self.record_history();
+        self.total_increments += times as u64;
 
         let caller = env::predecessor_account_id();
-        let event = format!(
-            "{{\"event\":\"decrement\",\"by\":\"{}\",\"value\":{}}}",
-            caller, self.value
-        );
-        env::log_str(&event);
-        self.event_log.push(&event);
+        self.enforce_cooldown(&caller);
+        self.record_user_increments(&caller, times as u64);
+
+        self.log_event(CounterEvent::IncrementMany {
+            by: caller,
+            times,
+            step: self.step,
+            value: self.value,
+            saturated,
+        });
+
+        self.value
     }
 
-    /// Increment by custom amount
-    pub fn increment_by(&mut self, amount: i64) {
-        self.value = self.value.checked_add(amount).expect("Overflow error");
+    /// Decrement counter by the configured step, returning the new value
+    pub fn decrement(&mut self) -> i64 {
+        self.assert_decrement_allowed();
+        self.assert_not_paused();
+        self.assert_whitelisted();
+        let (value, saturated) = self.apply_sub(self.step);
+        self.assert_within_bounds(value);
+        self.record_delta(value - self.value);
+        self.value = value;
+        self.record_history();
+        self.total_decrements += 1;
 
         let caller = env::predecessor_account_id();
-        let event = format!(
-            "{{\"event\":\"increment_by\",\"by\":\"{}\",\"amount\":{},\"value\":{}}}",
-            caller, amount, self.value
-        );
-        env::log_str(&event);
-        self.event_log.push(&event);
+        self.record_user_decrement(&caller);
+        self.log_event(CounterEvent::Decrement {
+            by: caller,
+            step: self.step,
+            value: self.value,
+            saturated,
+        });
+
+        self.value
     }
 
-    /// Reset counter to zero (owner only)
-    pub fn reset(&mut self) {
-        self.assert_owner();
-        self.value = 0;
+    /// Increment by custom amount, returning the new value
+    pub fn increment_by(&mut self, amount: i64) -> i64 {
+        if amount < 0 {
+            self.assert_decrement_allowed();
+        }
+        self.assert_not_paused();
+        self.assert_whitelisted();
+        let (value, saturated) = self.apply_add(amount);
+        self.assert_within_bounds(value);
+        self.record_delta(value - self.value);
+        self.value = value;
+        self.record_history();
+
+        let caller = env::predecessor_account_id();
+        self.enforce_cooldown(&caller);
+        self.log_event(CounterEvent::IncrementBy {
+            by: caller,
+            amount,
+            value: self.value,
+            saturated,
+        });
+
+        self.value
+    }
+
+    /// Decrement by custom amount, returning the new value
+    pub fn decrement_by(&mut self, amount: i64) -> i64 {
+        self.assert_decrement_allowed();
+        self.assert_not_paused();
+        self.assert_whitelisted();
+        assert!(amount >= 0, "amount must be non-negative");
+        let (value, saturated) = self.apply_sub(amount);
+        self.assert_within_bounds(value);
+        self.record_delta(value - self.value);
+        self.value = value;
+        self.record_history();
+        self.total_decrements += 1;
+
+        let caller = env::predecessor_account_id();
+        self.record_user_decrement(&caller);
+        self.log_event(CounterEvent::DecrementBy {
+            by: caller,
+            amount,
+            value: self.value,
+            saturated,
+        });
+
+        self.value
+    }
+
+    /// Multiply by a positive `factor`, returning the new value
+    /// Always panics on overflow, regardless of the overflow mode
+    /// Zero and negative factors are rejected, as they would let any caller
+    /// reset the counter or flip its sign
+    pub fn multiply_by(&mut self, factor: i64) -> i64 {
+        self.assert_not_paused();
+        self.assert_whitelisted();
+        assert!(factor > 0, "factor must be positive");
+        let value = self.value.checked_mul(factor).expect("Overflow error");
+        // Scaling a negative value lowers it
+        if value < self.value {
+            self.assert_decrement_allowed();
+        }
+        self.assert_within_bounds(value);
+        self.record_set(self.value);
+        self.value = value;
+        self.record_history();
 
         let caller = env::predecessor_account_id();
-        let event = format!("{{\"event\":\"reset\",\"by\":\"{}\"}}", caller);
-        env::log_str(&event);
-        self.event_log.push(&event);
+        self.log_event(CounterEvent::MultiplyBy {
+            by: caller,
+            factor,
+            value: self.value,
+        });
+
+        self.value
     }
 
-    /// Set counter to specific value (owner only)
-    pub fn set_counter(&mut self, value: i64) {
+    /// Divide by `divisor` (owner only), returning the new value
+    /// Integer division truncating toward zero, so the remainder is lost
+    pub fn divide_by(&mut self, divisor: i64) -> i64 {
         self.assert_owner();
+        self.assert_not_paused();
+        assert!(divisor != 0, "Division by zero");
+        let value = self.value.checked_div(divisor).expect("Overflow error");
+        self.assert_within_bounds(value);
+        self.record_set(self.value);
         self.value = value;
+        self.record_history();
 
         let caller = env::predecessor_account_id();
-        let event = format!(
-            "{{\"event\":\"set_counter\",\"by\":\"{}\",\"value\":{}}}",
-            caller, value
-        );
-        env::log_str(&event);
-        self.event_log.push(&event);
+        self.log_event(CounterEvent::DivideBy {
+            by: caller,
+            divisor,
+            value: self.value,
+        });
+
+        self.value
     }
 
-    /// Get recent events (last 10)
-    pub fn get_recent_events(&self) -> Vec<String> {
-        let len = self.event_log.len();
-        let start = if len > 10 { len - 10 } else { 0 };
-        (start..len)
-            .map(|i| self.event_log.get(i).unwrap())
-            .collect()
+    /// Reset counter to the configured reset value (owner or admin), returning the new value
+    /// Remains callable while paused so the owner can recover
+    /// Unavailable while multisig is on; use `propose_action` instead
+    pub fn reset(&mut self) -> i64 {
+        self.assert_owner_or_admin();
+        self.assert_multisig_off();
+        self.apply_reset()
     }
 
-    /// Get all events
-    pub fn get_all_events(&self) -> Vec<String> {
-        (0..self.event_log.len())
-            .map(|i| self.event_log.get(i).unwrap())
-            .collect()
+    /// Set counter to specific value (owner or admin), returning the new value
+    /// Unavailable while multisig is on; use `propose_action` instead
+    pub fn set_counter(&mut self, value: i64) -> i64 {
+        self.assert_owner_or_admin();
+        self.assert_multisig_off();
+        self.apply_set_counter(value)
     }
 
-    /// Clear event log (owner only)
-    pub fn clear_events(&mut self) {
+    /// Require `threshold` of `owners` to confirm `reset`/`set_counter`/`undo` (owner only)
+    /// Only available while multisig is off; once on, changes to the co-owners
+    /// or threshold go through `propose_set_multisig`
+    pub fn set_multisig(&mut self, owners: Vec<AccountId>, threshold: u8) {
         self.assert_owner();
-        self.event_log.clear();
+        assert!(
+            self.threshold == 0,
+            "multisig enabled, use propose_set_multisig"
+        );
+        self.apply_set_multisig(owners, threshold);
     }
 
-    // Private helper functions
+    /// Propose a privileged action (co-owner only), returning its id
+    /// The proposal executes once `threshold` co-owners call `confirm_action`
+    pub fn propose_action(&mut self, kind: ActionKind, value: i64) -> u64 {
+        assert!(kind != ActionKind::SetMultisig, "use propose_set_multisig");
+        self.add_proposal(kind, value, Vec::new())
+    }
 
-    fn assert_owner(&self) {
-        assert_eq!(
-            env::predecessor_account_id(),
-            self.owner,
-            "Only owner can call this method"
+    /// Propose new co-owners and threshold (co-owner only), returning its id
+    /// A threshold of 0 turns multisig off once the proposal executes
+    pub fn propose_set_multisig(&mut self, owners: Vec<AccountId>, threshold: u8) -> u64 {
+        let mut owners = owners;
+        owners.sort();
+        owners.dedup();
+        assert!(
+            usize::from(threshold) <= owners.len(),
+            "threshold exceeds owner count"
         );
+        self.add_proposal(ActionKind::SetMultisig, i64::from(threshold), owners)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::testing_env;
+    /// Confirm a proposal (co-owner only), returning whether it executed
+    /// Only confirmations from current co-owners count towards the threshold
+    pub fn confirm_action(&mut self, id: u64) -> bool {
+        self.assert_multisig_owner();
+        let mut proposal = self.proposals.get(&id).expect("proposal not found");
+        assert!(!proposal.executed, "proposal already executed");
+        let caller = env::predecessor_account_id();
+        assert!(
+            !proposal.confirmations.contains(&caller),
+            "already confirmed"
+        );
+        proposal.confirmations.push(caller.clone());
 
-    fn get_context(predecessor: AccountId) -> VMContextBuilder {
-        let mut builder = VMContextBuilder::new();
-        builder.predecessor_account_id(predecessor);
-        builder
+        let confirmations = proposal
+            .confirmations
+            .iter()
+            .filter(|account_id| self.owners.contains(account_id))
+            .count() as u8;
+        self.log_event(CounterEvent::ActionConfirmed {
+            by: caller,
+            id,
+            confirmations,
+        });
+
+        proposal.executed = confirmations >= self.threshold;
+        self.proposals.insert(&id, &proposal);
+        if proposal.executed {
+            match proposal.kind {
+                ActionKind::Reset => {
+                    self.apply_reset();
+                }
+                ActionKind::SetCounter => {
+                    self.apply_set_counter(proposal.value);
+                }
+                ActionKind::SetMultisig => {
+                    self.apply_set_multisig(proposal.owners.clone(), proposal.value as u8);
+                }
+            }
+        }
+
+        proposal.executed
     }
 
-    #[test]
-    fn test_new() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
+    /// Revert the most recent mutation (owner only), returning the new value
+    /// Only one level is kept; activity totals are not rolled back
+    /// Unavailable while multisig is on
+    pub fn undo(&mut self) -> i64 {
+        self.assert_owner();
+        self.assert_multisig_off();
+        if let Some(previous) = self.last_was_set.take() {
+            self.value = previous;
+        } else if let Some(delta) = self.last_delta.take() {
+            self.value = self.value.checked_sub(delta).expect("Overflow error");
+        } else {
+            env::panic_str("nothing to undo");
+        }
+        self.record_history();
 
-        let contract = Counter::new(0);
-        assert_eq!(contract.get_counter(), 0);
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::Undo {
+            by: caller,
+            value: self.value,
+        });
+
+        self.value
     }
 
-    #[test]
-    fn test_increment() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
+    /// Set the value applied by `reset` (owner only)
+    pub fn set_reset_value(&mut self, reset_value: i64) {
+        self.assert_owner();
+        self.reset_value = reset_value;
 
-        let mut contract = Counter::new(5);
-        contract.increment();
-        assert_eq!(contract.get_counter(), 6);
-        assert_eq!(contract.get_total_increments(), 1);
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetResetValue {
+            by: caller,
+            reset_value,
+        });
     }
 
-    #[test]
-    fn test_decrement() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
+    /// Pause all counter mutations (owner only)
+    pub fn pause(&mut self) {
+        self.assert_owner();
+        self.paused = true;
 
-        let mut contract = Counter::new(5);
-        contract.decrement();
-        assert_eq!(contract.get_counter(), 4);
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::Paused { by: caller });
     }
 
-    #[test]
-    fn test_increment_by() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
+    /// Resume counter mutations (owner only)
+    pub fn unpause(&mut self) {
+        self.assert_owner();
+        self.paused = false;
 
-        let mut contract = Counter::new(10);
-        contract.increment_by(5);
-        assert_eq!(contract.get_counter(), 15);
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::Unpaused { by: caller });
     }
 
-    #[test]
-    fn test_reset() {
-        let context = get_context(accounts(0));
-        testing_env!(context.build());
+    /// Set the per-user increment cooldown in nanoseconds (owner only)
+    /// A cooldown of 0 disables the check
+    pub fn set_cooldown(&mut self, cooldown_ns: u64) {
+        self.assert_owner();
+        self.cooldown_ns = cooldown_ns;
 
-        let mut contract = Counter::new(42);
-        contract.reset();
-        assert_eq!(contract.get_counter(), 0);
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetCooldown {
+            by: caller,
+            cooldown_ns,
+        });
     }
 
-    #[test]
-    #[should_panic(expected = "Only owner can call this method")]
-    fn test_reset_not_owner() {
-        let mut context = get_context(accounts(0));
-        testing_env!(context.build());
+    /// Set the deposit `increment_paid` requires, in yoctoNEAR (owner only)
+    pub fn set_min_increment_deposit(&mut self, amount: U128) {
+        self.assert_owner();
+        self.min_increment_deposit = amount.0;
 
-        let mut contract = Counter::new(42);
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetMinIncrementDeposit { by: caller, amount });
+    }
 
-        // Change caller
-        context.predecessor_account_id(accounts(1));
-        testing_env!(context.build());
+    /// Restrict anything that lowers the value to the owner (owner only):
+    /// `decrement`, `decrement_by`, a negative `increment_by` and a
+    /// `multiply_by` of a negative value. Raising the value stays open
+    pub fn set_decrement_owner_only(&mut self, enabled: bool) {
+        self.assert_owner();
+        self.decrement_owner_only = enabled;
 
-        contract.reset();
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetDecrementOwnerOnly {
+            by: caller,
+            enabled,
+        });
+    }
+
+    /// Set the step applied by `increment` and `decrement` (owner only)
+    pub fn set_step(&mut self, step: i64) {
+        self.assert_owner();
+        assert!(step > 0, "step must be positive");
+        self.step = step;
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetStep { by: caller, step });
+    }
+
+    /// Set the overflow behavior (owner only)
+    pub fn set_overflow_mode(&mut self, mode: OverflowMode) {
+        self.assert_owner();
+        self.overflow_mode = mode;
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetOverflowMode { by: caller, mode });
+    }
+
+    /// Set optional (min, max) bounds on the counter value (owner only)
+    pub fn set_bounds(&mut self, min: Option<i64>, max: Option<i64>) {
+        self.assert_owner();
+        if let (Some(min), Some(max)) = (min, max) {
+            assert!(min <= max, "min bound must not exceed max bound");
+        }
+        self.min_bound = min;
+        self.max_bound = max;
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetBounds {
+            by: caller,
+            min,
+            max,
+        });
+    }
+
+    /// Grant admin rights to an account (owner only)
+    pub fn add_admin(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.admins.insert(&account_id);
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::AddAdmin {
+            by: caller,
+            account_id,
+        });
+    }
+
+    /// Revoke admin rights from an account (owner only)
+    pub fn remove_admin(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.admins.remove(&account_id);
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::RemoveAdmin {
+            by: caller,
+            account_id,
+        });
+    }
+
+    /// Add an account to the whitelist (owner only)
+    pub fn add_to_whitelist(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.whitelist.insert(&account_id, &true);
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::AddToWhitelist {
+            by: caller,
+            account_id,
+        });
+    }
+
+    /// Remove an account from the whitelist (owner only)
+    pub fn remove_from_whitelist(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.whitelist.remove(&account_id);
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::RemoveFromWhitelist {
+            by: caller,
+            account_id,
+        });
+    }
+
+    /// Enable or disable whitelist enforcement (owner only)
+    pub fn set_whitelist_enabled(&mut self, enabled: bool) {
+        self.assert_owner();
+        self.whitelist_enabled = enabled;
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetWhitelistEnabled {
+            by: caller,
+            enabled,
+        });
+    }
+
+    /// Propose a new owner (owner only)
+    /// The proposed account must call `accept_owner` to complete the transfer
+    pub fn propose_owner(&mut self, new_owner: AccountId) {
+        self.assert_owner();
+        self.pending_owner = Some(new_owner.clone());
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::OwnerProposed {
+            by: caller,
+            new_owner,
+        });
+    }
+
+    /// Accept a pending ownership proposal (pending owner only)
+    pub fn accept_owner(&mut self) {
+        let caller = env::predecessor_account_id();
+        assert_eq!(
+            Some(&caller),
+            self.pending_owner.as_ref(),
+            "Only pending owner can accept ownership"
+        );
+
+        let previous = std::mem::replace(&mut self.owner, caller.clone());
+        self.pending_owner = None;
+
+        self.log_event(CounterEvent::OwnerAccepted {
+            by: caller,
+            previous_owner: previous,
+        });
+    }
+
+    /// Create a new named counter starting at zero
+    pub fn create_counter(&mut self, name: String) {
+        assert!(!self.counters.contains_key(&name), "Counter already exists");
+        self.counters.insert(&name, &0);
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::CreateCounter { by: caller, name });
+    }
+
+    /// Increment a named counter by 1, returning its new value
+    pub fn increment_named(&mut self, name: String) -> i64 {
+        self.assert_not_paused();
+        let value = self
+            .get_named(name.clone())
+            .checked_add(1)
+            .expect("Overflow error");
+        self.counters.insert(&name, &value);
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::IncrementNamed {
+            by: caller,
+            name,
+            value,
+        });
+
+        value
+    }
+
+    /// Decrement a named counter by 1, returning its new value
+    pub fn decrement_named(&mut self, name: String) -> i64 {
+        self.assert_not_paused();
+        let value = self
+            .get_named(name.clone())
+            .checked_sub(1)
+            .expect("Underflow error");
+        self.counters.insert(&name, &value);
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::DecrementNamed {
+            by: caller,
+            name,
+            value,
+        });
+
+        value
+    }
+
+    /// Get the value of a named counter
+    pub fn get_named(&self, name: String) -> i64 {
+        self.counters.get(&name).expect("Counter not found")
+    }
+
+    /// Increment the counter deployed at `target`
+    /// The outcome is reported by `on_increment_remote_complete`
+    pub fn increment_remote(&mut self, target: AccountId) -> Promise {
+        ext_counter::ext(target.clone())
+            .with_static_gas(GAS_FOR_REMOTE_INCREMENT)
+            .increment()
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_REMOTE_INCREMENT_CALLBACK)
+                    .on_increment_remote_complete(target),
+            )
+    }
+
+    /// Callback for `increment_remote`, returning the remote value on success
+    #[private]
+    pub fn on_increment_remote_complete(
+        &mut self,
+        target: AccountId,
+        #[callback_result] result: Result<i64, PromiseError>,
+    ) -> Option<i64> {
+        match result {
+            Ok(value) => {
+                self.log_event(CounterEvent::IncrementRemote {
+                    target,
+                    success: true,
+                    value: Some(value),
+                });
+                Some(value)
+            }
+            Err(_) => {
+                self.log_event(CounterEvent::IncrementRemote {
+                    target,
+                    success: false,
+                    value: None,
+                });
+                None
+            }
+        }
+    }
+
+    /// Get recent events (last 10)
+    pub fn get_recent_events(&self) -> Vec<String> {
+        self.get_recent_events_n(10)
+    }
+
+    /// Get the last `n` events, oldest first
+    /// Returns the whole log when `n` exceeds its length
+    pub fn get_recent_events_n(&self, n: u64) -> Vec<String> {
+        assert!(n <= MAX_RECENT_EVENTS, "window too large");
+        let len = self.event_log.len();
+        let start = len.saturating_sub(n);
+        (start..len).map(|i| self.event_at(i)).collect()
+    }
+
+    /// Get a page of events starting at `from_index`
+    /// Returns an empty vector when `from_index` is past the end of the log
+    pub fn get_events(&self, from_index: u64, limit: u64) -> Vec<String> {
+        let len = self.event_log.len();
+        let end = from_index.saturating_add(limit).min(len);
+        (from_index.min(end)..end)
+            .map(|i| self.event_at(i))
+            .collect()
+    }
+
+    /// Get a page of the events named `kind`, oldest first
+    /// `from_index` and `limit` count matching events only. The whole log
+    /// is scanned, so large logs are better filtered off-chain via `get_events`
+    pub fn get_events_by_kind(&self, kind: String, from_index: u64, limit: u64) -> Vec<String> {
+        assert!(limit <= MAX_EVENTS_BY_KIND, "limit too large");
+        (0..self.event_log.len())
+            .map(|i| self.event_at(i))
+            .filter(|event| {
+                serde_json::from_str::<Nep297Kind>(event)
+                    .map(|parsed| parsed.event == kind)
+                    .unwrap_or(false)
+            })
+            .skip(from_index as usize)
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Get the number of events in the log
+    pub fn get_events_count(&self) -> u64 {
+        self.event_log.len()
+    }
+
+    /// Get all events
+    /// Only reflects persisted history: events emitted while `persist_events`
+    /// was off appear in the transaction logs but not here
+    pub fn get_all_events(&self) -> Vec<String> {
+        (0..self.event_log.len())
+            .map(|i| self.event_at(i))
+            .collect()
+    }
+
+    /// Get the maximum number of events retained in the log
+    pub fn get_max_events(&self) -> u64 {
+        self.max_events
+    }
+
+    /// Whether events are persisted to the on-chain log
+    pub fn is_persist_events(&self) -> bool {
+        self.persist_events
+    }
+
+    /// Turn event persistence on or off (owner only)
+    /// When off, events are still emitted via `env::log_str` but not stored,
+    /// saving storage and gas on every mutation
+    pub fn set_persist_events(&mut self, enabled: bool) {
+        self.assert_owner();
+        self.persist_events = enabled;
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetPersistEvents {
+            by: caller,
+            enabled,
+        });
+    }
+
+    /// Set the maximum number of events retained (owner only)
+    /// Shrinking the cap drops the oldest events immediately
+    pub fn set_max_events(&mut self, max_events: u64) {
+        self.assert_owner();
+        assert!(max_events > 0, "max_events must be positive");
+
+        // Rewrite the log oldest-first so the ring restarts at slot 0
+        let len = self.event_log.len();
+        if self.event_head != 0 || len > max_events {
+            let retained: Vec<String> = (len.saturating_sub(max_events)..len)
+                .map(|i| self.event_at(i))
+                .collect();
+            self.event_log.clear();
+            self.event_log.extend(retained);
+            self.event_head = 0;
+        }
+        self.max_events = max_events;
+    }
+
+    /// Clear event log (owner or admin)
+    /// The storage stake released by the cleared entries is refunded to the owner
+    pub fn clear_events(&mut self) {
+        self.assert_owner_or_admin();
+        let usage_before = env::storage_usage();
+        self.event_log.clear();
+        self.event_head = 0;
+
+        let freed_bytes = usage_before.saturating_sub(env::storage_usage());
+        let refund = env::storage_byte_cost().saturating_mul(freed_bytes as u128);
+        if !refund.is_zero() {
+            let _ = Promise::new(self.owner.clone()).transfer(refund);
+        }
+    }
+
+    /// Send `amount` yoctoNEAR held by the contract to the owner (owner only)
+    /// The stake backing current storage usage, including the withdraw event
+    /// itself, is kept in reserve
+    pub fn withdraw(&mut self, amount: U128) -> Promise {
+        self.assert_owner();
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::Withdraw { by: caller, amount });
+
+        let reserve = env::storage_byte_cost().saturating_mul(env::storage_usage() as u128);
+        let available = env::account_balance().saturating_sub(reserve);
+        assert!(
+            amount.0 <= available.as_yoctonear(),
+            "amount exceeds withdrawable balance"
+        );
+
+        Promise::new(self.owner.clone()).transfer(NearToken::from_yoctonear(amount.0))
+    }
+
+    /// Get a page of the value history as `(block_timestamp, value)`, oldest first
+    pub fn get_history(&self, from_index: u64, limit: u64) -> Vec<(u64, i64)> {
+        let len = self.history.len();
+        let end = from_index.saturating_add(limit).min(len);
+        (from_index.min(end)..end)
+            .map(|i| self.history_at(i))
+            .collect()
+    }
+
+    /// Get the maximum number of history entries retained
+    pub fn get_max_history(&self) -> u64 {
+        self.max_history
+    }
+
+    /// Set the maximum number of history entries retained (owner only)
+    /// Shrinking the cap drops the oldest entries immediately
+    pub fn set_max_history(&mut self, max_history: u64) {
+        self.assert_owner();
+        assert!(max_history > 0, "max_history must be positive");
+
+        let len = self.history.len();
+        if self.history_head != 0 || len > max_history {
+            let retained: Vec<(u64, i64)> = (len.saturating_sub(max_history)..len)
+                .map(|i| self.history_at(i))
+                .collect();
+            self.history.clear();
+            self.history.extend(retained);
+            self.history_head = 0;
+        }
+        self.max_history = max_history;
+    }
+
+    /// Consider `accounts` for the top contributor spot using their stored
+    /// increments (owner only), returning the resulting top contributor
+    /// `migrate` cannot enumerate `user_increments`, so call this after an
+    /// upgrade with the accounts known to have incremented, in batches
+    pub fn seed_top_contributor(&mut self, accounts: Vec<AccountId>) -> Option<(AccountId, u64)> {
+        self.assert_owner();
+        assert!(accounts.len() <= MAX_BATCH_ACCOUNTS, "too many accounts");
+        for account_id in accounts.iter() {
+            let count = self.user_increments.get(account_id).unwrap_or(0);
+            self.update_top_contributor(account_id, count);
+        }
+        self.top_contributor.clone()
+    }
+
+    /// Add those of `accounts` with stored activity to the participant list
+    /// (owner only), returning the resulting participant count
+    /// `migrate` cannot enumerate `user_increments`, so call this after an
+    /// upgrade with the accounts known to have incremented, in batches
+    pub fn seed_participants(&mut self, accounts: Vec<AccountId>) -> u64 {
+        self.assert_owner();
+        assert!(accounts.len() <= MAX_BATCH_ACCOUNTS, "too many accounts");
+        for account_id in accounts.iter() {
+            if self.user_increments.get(account_id).is_some()
+                || self.user_decrements.get(account_id).is_some()
+            {
+                self.record_participant(account_id);
+            }
+        }
+        self.participants.len()
+    }
+
+    /// Get the contract's current storage usage in bytes
+    pub fn storage_usage(&self) -> u64 {
+        env::storage_usage()
+    }
+
+    // Private helper functions
+
+    fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner,
+            "Only owner can call this method"
+        );
+    }
+
+    /// Emit a NEP-297 event (`EVENT_JSON:{...}`) and append it to the event log
+    /// unless persistence is off
+    /// The current block height and timestamp are attached to the event data
+    fn log_event(&mut self, event: CounterEvent) {
+        let event = serde_json::to_string(&Nep297Event {
+            standard: EVENT_STANDARD,
+            version: EVENT_STANDARD_VERSION,
+            event: event.name(),
+            data: [EventData {
+                event: &event,
+                block_height: env::block_height(),
+                timestamp: env::block_timestamp(),
+            }],
+        })
+        .unwrap();
+        env::log_str(&format!("EVENT_JSON:{}", event));
+        if self.persist_events {
+            self.push_event(&event);
+        }
+    }
+
+    /// Append to the event log, evicting the oldest entry once full
+    fn push_event(&mut self, event: &String) {
+        if self.event_log.len() < self.max_events {
+            self.event_log.push(event);
+        } else {
+            self.event_log.replace(self.event_head, event);
+            self.event_head = (self.event_head + 1) % self.event_log.len();
+        }
+    }
+
+    /// Read the event at logical index `index` (0 = oldest)
+    fn event_at(&self, index: u64) -> String {
+        let slot = (self.event_head + index) % self.event_log.len();
+        self.event_log.get(slot).unwrap()
+    }
+
+    /// Append the current value to the history, evicting the oldest entry once full
+    fn record_history(&mut self) {
+        let entry = (env::block_timestamp(), self.value);
+        if self.history.len() < self.max_history {
+            self.history.push(&entry);
+        } else {
+            self.history.replace(self.history_head, &entry);
+            self.history_head = (self.history_head + 1) % self.history.len();
+        }
+    }
+
+    /// Read the history entry at logical index `index` (0 = oldest)
+    fn history_at(&self, index: u64) -> (u64, i64) {
+        let slot = (self.history_head + index) % self.history.len();
+        self.history.get(slot).unwrap()
+    }
+
+    /// Remember `account_id` as a participant the first time it acts
+    fn record_participant(&mut self, account_id: &AccountId) {
+        if self.participant_set.insert(account_id) {
+            self.participants.push(account_id);
+        }
+    }
+
+    /// Add `times` to the account's increments, taking the top contributor
+    /// spot once its count surpasses the current leader's
+    fn record_user_increments(&mut self, account_id: &AccountId, times: u64) {
+        self.record_participant(account_id);
+        let user_count = self.user_increments.get(account_id).unwrap_or(0) + times;
+        self.user_increments.insert(account_id, &user_count);
+        self.update_top_contributor(account_id, user_count);
+    }
+
+    /// Make `account_id` the top contributor if `count` surpasses the leader's
+    fn update_top_contributor(&mut self, account_id: &AccountId, count: u64) {
+        let leads = match &self.top_contributor {
+            Some((leader, top)) => leader == account_id || count > *top,
+            None => count > 0,
+        };
+        if leads {
+            self.top_contributor = Some((account_id.clone(), count));
+        }
+    }
+
+    fn record_user_decrement(&mut self, account_id: &AccountId) {
+        self.record_participant(account_id);
+        let user_count = self.user_decrements.get(account_id).unwrap_or(0);
+        self.user_decrements.insert(account_id, &(user_count + 1));
+    }
+
+    /// Panic if `account_id` acted within the cooldown window, else record now
+    fn enforce_cooldown(&mut self, account_id: &AccountId) {
+        if self.cooldown_ns == 0 {
+            return;
+        }
+        let now = env::block_timestamp();
+        if let Some(last) = self.last_action.get(account_id) {
+            assert!(
+                now.saturating_sub(last) >= self.cooldown_ns,
+                "cooldown not elapsed"
+            );
+        }
+        self.last_action.insert(account_id, &now);
+    }
+
+    /// Add `amount` to the value per the overflow mode, returning
+    /// the result and whether it was clamped
+    fn apply_add(&self, amount: i64) -> (i64, bool) {
+        match (
+            CounterState::new(self.value).increment_by(amount),
+            self.overflow_mode,
+        ) {
+            (Ok(value), _) => (value, false),
+            (Err(_), OverflowMode::Saturate) => (self.value.saturating_add(amount), true),
+            (Err(err), OverflowMode::Panic) => env::panic_str(err.as_str()),
+        }
+    }
+
+    /// Subtract `amount` from the value per the overflow mode, returning
+    /// the result and whether it was clamped
+    fn apply_sub(&self, amount: i64) -> (i64, bool) {
+        match (
+            CounterState::new(self.value).decrement_by(amount),
+            self.overflow_mode,
+        ) {
+            (Ok(value), _) => (value, false),
+            (Err(_), OverflowMode::Saturate) => (self.value.saturating_sub(amount), true),
+            (Err(err), OverflowMode::Panic) => env::panic_str(err.as_str()),
+        }
+    }
+
+    /// Reset to `reset_value`; remains available while paused
+    fn apply_reset(&mut self) -> i64 {
+        self.record_set(self.value);
+        self.value = self.reset_value;
+        self.record_history();
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::Reset {
+            by: caller,
+            reset_value: self.reset_value,
+        });
+
+        self.value
+    }
+
+    fn apply_set_multisig(&mut self, owners: Vec<AccountId>, threshold: u8) {
+        self.owners.clear();
+        for account_id in owners.iter() {
+            self.owners.insert(account_id);
+        }
+        assert!(
+            u64::from(threshold) <= self.owners.len(),
+            "threshold exceeds owner count"
+        );
+        self.threshold = threshold;
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetMultisig {
+            by: caller,
+            owners: self.owners.to_vec(),
+            threshold,
+        });
+    }
+
+    fn add_proposal(&mut self, kind: ActionKind, value: i64, owners: Vec<AccountId>) -> u64 {
+        self.assert_multisig_owner();
+        let id = self.next_proposal_id;
+        self.next_proposal_id += 1;
+        self.proposals.insert(
+            &id,
+            &Proposal {
+                kind,
+                value,
+                owners,
+                confirmations: Vec::new(),
+                executed: false,
+            },
+        );
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::ActionProposed {
+            by: caller,
+            id,
+            kind,
+            value,
+        });
+
+        id
+    }
+
+    fn apply_set_counter(&mut self, value: i64) -> i64 {
+        self.assert_not_paused();
+        self.assert_within_bounds(value);
+        self.record_set(self.value);
+        self.value = value;
+        self.record_history();
+
+        let caller = env::predecessor_account_id();
+        self.log_event(CounterEvent::SetCounter { by: caller, value });
+
+        self.value
+    }
+
+    fn record_delta(&mut self, delta: i64) {
+        self.last_delta = Some(delta);
+        self.last_was_set = None;
+    }
+
+    fn record_set(&mut self, previous: i64) {
+        self.last_was_set = Some(previous);
+        self.last_delta = None;
+    }
+
+    fn assert_not_paused(&self) {
+        assert!(!self.paused, "Contract is paused");
+    }
+
+    fn assert_decrement_allowed(&self) {
+        if self.decrement_owner_only {
+            self.assert_owner();
+        }
+    }
+
+    fn assert_owner_or_admin(&self) {
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller == self.owner || self.admins.contains(&caller),
+            "Only owner or admin can call this method"
+        );
+    }
+
+    fn assert_multisig_off(&self) {
+        assert!(self.threshold == 0, "multisig enabled, use propose_action");
+    }
+
+    fn assert_multisig_owner(&self) {
+        assert!(self.threshold > 0, "multisig disabled");
+        assert!(
+            self.owners.contains(&env::predecessor_account_id()),
+            "Only multisig owners can call this method"
+        );
+    }
+
+    fn assert_whitelisted(&self) {
+        if !self.whitelist_enabled {
+            return;
+        }
+        let caller = env::predecessor_account_id();
+        assert!(
+            caller == self.owner || self.whitelist.get(&caller).unwrap_or(false),
+            "account not whitelisted"
+        );
+    }
+
+    fn assert_within_bounds(&self, value: i64) {
+        if let Err(err) = CounterState::with_bounds(self.value, self.min_bound, self.max_bound)
+            .check_bounds(value)
+        {
+            env::panic_str(err.as_str());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::serde_json::{json, Value};
+    use near_sdk::test_utils::{accounts, get_created_receipts, get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn get_context(predecessor: AccountId) -> VMContextBuilder {
+        let mut builder = VMContextBuilder::new();
+        builder.predecessor_account_id(predecessor);
+        builder
+    }
+
+    #[test]
+    fn test_new() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let contract = Counter::new(0);
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    fn test_increment() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(5);
+        contract.increment();
+        assert_eq!(contract.get_counter(), 6);
+        assert_eq!(contract.get_total_increments(), 1);
+    }
+
+    #[test]
+    fn test_decrement() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(5);
+        contract.decrement();
+        assert_eq!(contract.get_counter(), 4);
+    }
+
+    #[test]
+    fn test_increment_by() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.increment_by(5);
+        assert_eq!(contract.get_counter(), 15);
+    }
+
+    #[test]
+    fn test_decrement_by() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.decrement_by(4);
+        assert_eq!(contract.get_counter(), 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "Underflow error")]
+    fn test_decrement_by_underflow() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(i64::MIN + 1);
+        contract.decrement_by(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "amount must be non-negative")]
+    fn test_decrement_by_negative_amount() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.decrement_by(-5);
+    }
+
+    #[test]
+    fn test_new_with_step() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new_with_step(0, 5);
+        assert_eq!(contract.get_step(), 5);
+        contract.increment();
+        contract.increment();
+        assert_eq!(contract.get_counter(), 10);
+        contract.decrement();
+        assert_eq!(contract.get_counter(), 5);
+    }
+
+    #[test]
+    fn test_set_step() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert_eq!(contract.get_step(), 1);
+        contract.increment();
+        assert_eq!(contract.get_counter(), 1);
+
+        contract.set_step(3);
+        assert_eq!(contract.get_step(), 3);
+        contract.increment();
+        assert_eq!(contract.get_counter(), 4);
+        contract.decrement();
+        assert_eq!(contract.get_counter(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be positive")]
+    fn test_set_step_zero() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_step(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must be positive")]
+    fn test_set_step_negative() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_step(-2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_set_step_not_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.set_step(2);
+    }
+
+    #[test]
+    fn test_unbounded_by_default() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert_eq!(contract.get_bounds(), (None, None));
+        contract.increment_by(1_000_000);
+        contract.decrement_by(2_000_000);
+        assert_eq!(contract.get_counter(), -1_000_000);
+    }
+
+    #[test]
+    fn test_bounds_allow_equal() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_bounds(Some(0), Some(100));
+        assert_eq!(contract.get_bounds(), (Some(0), Some(100)));
+
+        contract.increment_by(99);
+        contract.increment();
+        assert_eq!(contract.get_counter(), 100);
+
+        contract.set_counter(0);
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "value exceeds max bound")]
+    fn test_increment_exceeds_max_bound() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(100);
+        contract.set_bounds(Some(0), Some(100));
+        contract.increment();
+    }
+
+    #[test]
+    #[should_panic(expected = "value below min bound")]
+    fn test_decrement_below_min_bound() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_bounds(Some(0), None);
+        contract.decrement();
+    }
+
+    #[test]
+    #[should_panic(expected = "value exceeds max bound")]
+    fn test_set_counter_exceeds_max_bound() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_bounds(None, Some(10));
+        contract.set_counter(11);
+    }
+
+    #[test]
+    #[should_panic(expected = "min bound must not exceed max bound")]
+    fn test_set_bounds_invalid() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_bounds(Some(10), Some(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn test_increment_while_paused() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.pause();
+        assert!(contract.is_paused());
+        contract.increment();
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn test_decrement_while_paused() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.pause();
+        contract.decrement();
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn test_increment_by_while_paused() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.pause();
+        contract.increment_by(5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn test_set_counter_while_paused() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.pause();
+        contract.set_counter(5);
+    }
+
+    #[test]
+    fn test_reset_while_paused() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(42);
+        contract.pause();
+        contract.reset();
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    fn test_unpause_resumes_mutations() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.pause();
+        contract.unpause();
+        assert!(!contract.is_paused());
+        contract.increment();
+        assert_eq!(contract.get_counter(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_pause_not_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.pause();
+    }
+
+    #[test]
+    fn test_increment_emits_nep297_event() {
+        let mut context = get_context(accounts(0));
+        context.block_height(77);
+        context.block_timestamp(1_700_000_000_000_000_000);
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(5);
+        contract.increment();
+
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        let payload = logs[0]
+            .strip_prefix("EVENT_JSON:")
+            .expect("event log must start with EVENT_JSON:");
+        let event: Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(
+            event,
+            json!({
+                "standard": "counter",
+                "version": "1.0.0",
+                "event": "increment",
+                "data": [{
+                    "by": accounts(0),
+                    "step": 1,
+                    "value": 6,
+                    "saturated": false,
+                    "block_height": 77,
+                    "timestamp": 1_700_000_000_000_000_000u64,
+                }],
+            })
+        );
+        assert_eq!(contract.get_all_events(), vec![payload.to_string()]);
+    }
+
+    #[test]
+    fn test_get_events_empty_log() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let contract = Counter::new(0);
+        assert_eq!(contract.get_events_count(), 0);
+        assert!(contract.get_events(0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_get_events_pagination() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        for _ in 0..5 {
+            contract.increment();
+        }
+        assert_eq!(contract.get_events_count(), 5);
+
+        let all = contract.get_all_events();
+        assert_eq!(contract.get_events(0, 2), all[0..2].to_vec());
+        assert_eq!(contract.get_events(2, 2), all[2..4].to_vec());
+        // Partial final page
+        assert_eq!(contract.get_events(4, 2), all[4..5].to_vec());
+    }
+
+    #[test]
+    fn test_get_events_out_of_range() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment();
+        assert!(contract.get_events(5, 10).is_empty());
+        assert!(contract.get_events(u64::MAX, u64::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_event_log_capped() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert_eq!(contract.get_max_events(), DEFAULT_MAX_EVENTS);
+        contract.set_max_events(3);
+
+        for _ in 0..7 {
+            contract.increment();
+            assert!(contract.get_events_count() <= 3);
+        }
+
+        let events = contract.get_all_events();
+        assert_eq!(events.len(), 3);
+        assert!(events[0].contains("\"value\":5"));
+        assert!(events[1].contains("\"value\":6"));
+        assert!(events[2].contains("\"value\":7"));
+        assert_eq!(contract.get_recent_events(), events);
+        assert_eq!(contract.get_events(1, 5), events[1..].to_vec());
+    }
+
+    #[test]
+    fn test_set_max_events_shrinks_wrapped_log() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_max_events(4);
+        for _ in 0..6 {
+            contract.increment();
+        }
+
+        contract.set_max_events(2);
+        let events = contract.get_all_events();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].contains("\"value\":5"));
+        assert!(events[1].contains("\"value\":6"));
+
+        contract.increment();
+        let events = contract.get_all_events();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].contains("\"value\":6"));
+        assert!(events[1].contains("\"value\":7"));
+    }
+
+    #[test]
+    #[should_panic(expected = "max_events must be positive")]
+    fn test_set_max_events_zero() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_max_events(0);
+    }
+
+    #[test]
+    fn test_mutations_return_new_value() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        assert_eq!(contract.increment(), 11);
+        assert_eq!(contract.get_counter(), 11);
+        assert_eq!(contract.decrement(), 10);
+        assert_eq!(contract.get_counter(), 10);
+        assert_eq!(contract.increment_by(5), 15);
+        assert_eq!(contract.get_counter(), 15);
+        assert_eq!(contract.decrement_by(3), 12);
+        assert_eq!(contract.get_counter(), 12);
+        assert_eq!(contract.set_counter(99), 99);
+        assert_eq!(contract.get_counter(), 99);
+        assert_eq!(contract.reset(), 0);
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    fn test_total_decrements() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.decrement();
+        contract.decrement();
+        contract.decrement_by(3);
+        contract.increment();
+        assert_eq!(contract.get_total_decrements(), 3);
+        assert_eq!(
+            contract.get_stats(),
+            CounterStats {
+                value: 6,
+                total_increments: 1,
+                total_decrements: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_user_activity() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment();
+        contract.increment();
+        contract.decrement();
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.decrement();
+        contract.decrement_by(2);
+        contract.increment();
+
+        assert_eq!(contract.get_user_increments(accounts(0)), 2);
+        assert_eq!(contract.get_user_decrements(accounts(0)), 1);
+        assert_eq!(contract.get_user_activity(accounts(0)), (2, 1));
+        assert_eq!(contract.get_user_activity(accounts(1)), (1, 2));
+        assert_eq!(contract.get_user_activity(accounts(2)), (0, 0));
+        assert_eq!(contract.get_participant_count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "cooldown not elapsed")]
+    fn test_cooldown_blocks_rapid_increments() {
+        let mut context = get_context(accounts(0));
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_cooldown(500);
+        assert_eq!(contract.get_cooldown(), 500);
+        contract.increment();
+
+        context.block_timestamp(1_499);
+        testing_env!(context.build());
+        contract.increment_by(2);
+    }
+
+    #[test]
+    fn test_cooldown_elapsed() {
+        let mut context = get_context(accounts(0));
+        context.block_timestamp(1_000);
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_cooldown(500);
+        contract.increment();
+
+        // Other accounts are not affected by this user's cooldown
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment();
+
+        context.predecessor_account_id(accounts(0));
+        context.block_timestamp(1_500);
+        testing_env!(context.build());
+        contract.increment();
+        assert_eq!(contract.get_counter(), 3);
+    }
+
+    #[test]
+    fn test_cooldown_disabled_by_default() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment();
+        contract.increment();
+        contract.increment_by(3);
+        assert_eq!(contract.get_counter(), 5);
+    }
+
+    #[test]
+    fn test_named_counters_are_independent() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(7);
+        contract.create_counter("likes".to_string());
+        contract.create_counter("views".to_string());
+
+        assert_eq!(contract.increment_named("likes".to_string()), 1);
+        contract.increment_named("likes".to_string());
+        contract.increment_named("views".to_string());
+        assert_eq!(contract.decrement_named("views".to_string()), 0);
+
+        assert_eq!(contract.get_named("likes".to_string()), 2);
+        assert_eq!(contract.get_named("views".to_string()), 0);
+        assert_eq!(contract.get_counter(), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter already exists")]
+    fn test_create_counter_duplicate() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.create_counter("likes".to_string());
+        contract.create_counter("likes".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter not found")]
+    fn test_increment_named_missing() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment_named("missing".to_string());
+    }
+
+    #[test]
+    fn test_migrate_from_old_layout() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut user_increments = LookupMap::new(b"u");
+        user_increments.insert(&accounts(1), &3);
+        let mut event_log = Vector::new(b"e");
+        event_log.push(&"{\"event\":\"increment\"}".to_string());
+        env::state_write(&OldCounter {
+            value: 42,
+            owner: accounts(0),
+            total_increments: 3,
+            user_increments,
+            event_log,
+        });
+
+        let mut contract = Counter::migrate();
+        assert_eq!(contract.get_counter(), 42);
+        assert_eq!(contract.get_owner(), accounts(0));
+        assert_eq!(contract.get_total_increments(), 3);
+        assert_eq!(contract.get_user_increments(accounts(1)), 3);
+        assert_eq!(contract.get_events_count(), 1);
+
+        // New fields get sensible defaults
+        assert_eq!(contract.get_pending_owner(), None);
+        assert_eq!(contract.get_step(), 1);
+        assert_eq!(contract.get_bounds(), (None, None));
+        assert!(!contract.is_paused());
+        assert_eq!(contract.get_total_decrements(), 0);
+        assert_eq!(contract.get_cooldown(), 0);
+        assert_eq!(contract.get_max_events(), DEFAULT_MAX_EVENTS);
+
+        assert_eq!(contract.increment(), 43);
+        assert_eq!(contract.get_events_count(), 2);
+    }
+
+    #[test]
+    fn test_migrate_then_seed_top_contributor() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut user_increments = LookupMap::new(b"u");
+        user_increments.insert(&accounts(1), &3);
+        user_increments.insert(&accounts(2), &5);
+        env::state_write(&OldCounter {
+            value: 8,
+            owner: accounts(0),
+            total_increments: 8,
+            user_increments,
+            event_log: Vector::new(b"e"),
+        });
+
+        let mut contract = Counter::migrate();
+        assert_eq!(contract.get_top_contributor(), None);
+
+        // The first post-upgrade increment leads only until the seed runs
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        contract.increment();
+        assert_eq!(contract.get_top_contributor(), Some((accounts(3), 1)));
+
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        assert_eq!(
+            contract.seed_top_contributor(vec![accounts(1), accounts(2), accounts(4)]),
+            Some((accounts(2), 5))
+        );
+        assert_eq!(contract.get_top_contributor(), Some((accounts(2), 5)));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment();
+        contract.increment();
+        contract.increment();
+        assert_eq!(contract.get_top_contributor(), Some((accounts(1), 6)));
+    }
+
+    #[test]
+    fn test_migrate_then_seed_participants() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut user_increments = LookupMap::new(b"u");
+        user_increments.insert(&accounts(1), &3);
+        user_increments.insert(&accounts(2), &5);
+        env::state_write(&OldCounter {
+            value: 8,
+            owner: accounts(0),
+            total_increments: 8,
+            user_increments,
+            event_log: Vector::new(b"e"),
+        });
+
+        let mut contract = Counter::migrate();
+        assert_eq!(contract.get_participant_count(), 0);
+
+        // Pre-upgrade incrementers are recorded the first time they act again
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment();
+        assert_eq!(contract.get_participants(0, 10), vec![accounts(1)]);
+
+        // Seeding skips known participants and accounts without activity
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        assert_eq!(
+            contract.seed_participants(vec![accounts(1), accounts(2), accounts(4)]),
+            2
+        );
+        assert_eq!(
+            contract.get_participants(0, 10),
+            vec![accounts(1), accounts(2)]
+        );
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.decrement();
+        assert_eq!(contract.get_participant_count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_seed_participants_owner_only() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.seed_participants(vec![accounts(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_seed_top_contributor_owner_only() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.seed_top_contributor(vec![accounts(1)]);
+    }
+
+    #[test]
+    fn test_increment_remote_schedules_call_and_callback() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        let _ = contract.increment_remote(accounts(1));
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].receiver_id, accounts(1));
+    }
+
+    #[test]
+    fn test_on_increment_remote_complete_success() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        let result = contract.on_increment_remote_complete(accounts(1), Ok(8));
+        assert_eq!(result, Some(8));
+        assert!(contract.get_recent_events()[0].contains("\"success\":true"));
+        // The local counter is untouched
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    fn test_on_increment_remote_complete_failure() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        let result = contract.on_increment_remote_complete(accounts(1), Err(PromiseError::Failed));
+        assert_eq!(result, None);
+        assert!(contract.get_recent_events()[0].contains("\"success\":false"));
+    }
+
+    #[test]
+    fn test_reset() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(42);
+        contract.reset();
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner or admin can call this method")]
+    fn test_reset_not_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(42);
+
+        // Change caller
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.reset();
+    }
+
+    #[test]
+    fn test_propose_and_accept_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.propose_owner(accounts(1));
+        assert_eq!(contract.get_pending_owner(), Some(accounts(1)));
+        assert_eq!(contract.get_owner(), accounts(0));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.accept_owner();
+        assert_eq!(contract.get_owner(), accounts(1));
+        assert_eq!(contract.get_pending_owner(), None);
+
+        // New owner can call owner-only methods
+        contract.reset();
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only pending owner can accept ownership")]
+    fn test_accept_owner_wrong_caller() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.propose_owner(accounts(1));
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+
+        contract.accept_owner();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only pending owner can accept ownership")]
+    fn test_accept_owner_without_proposal() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.accept_owner();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_propose_owner_not_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.propose_owner(accounts(1));
+    }
+
+    #[test]
+    fn test_propose_owner_overwrites_pending() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.propose_owner(accounts(1));
+        contract.propose_owner(accounts(2));
+        assert_eq!(contract.get_pending_owner(), Some(accounts(2)));
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.accept_owner();
+        assert_eq!(contract.get_owner(), accounts(2));
+    }
+
+    #[test]
+    fn test_admin_can_reset_and_set() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(42);
+        contract.add_admin(accounts(1));
+        assert!(contract.is_admin(accounts(1)));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.set_counter(7);
+        assert_eq!(contract.get_counter(), 7);
+        contract.reset();
+        assert_eq!(contract.get_counter(), 0);
+        contract.clear_events();
+        assert_eq!(contract.get_events_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner or admin can call this method")]
+    fn test_removed_admin_cannot_reset() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(42);
+        contract.add_admin(accounts(1));
+        contract.remove_admin(accounts(1));
+        assert!(!contract.is_admin(accounts(1)));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.reset();
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_admin_cannot_manage_admins() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.add_admin(accounts(1));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.add_admin(accounts(2));
+    }
+
+    #[test]
+    fn test_undo_increment() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.increment_by(5);
+        assert_eq!(contract.undo(), 10);
+        assert_eq!(contract.get_counter(), 10);
+
+        contract.decrement();
+        assert_eq!(contract.undo(), 10);
+    }
+
+    #[test]
+    fn test_undo_set_counter() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.increment();
+        contract.set_counter(500);
+        assert_eq!(contract.undo(), 11);
+
+        contract.reset();
+        assert_eq!(contract.undo(), 11);
+        assert!(contract
+            .get_recent_events()
+            .last()
+            .unwrap()
+            .contains("\"undo\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing to undo")]
+    fn test_undo_empty() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.undo();
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing to undo")]
+    fn test_undo_twice() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.increment();
+        contract.undo();
+        contract.undo();
+    }
+
+    #[test]
+    fn test_whitelist_disabled_by_default() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert!(!contract.is_whitelist_enabled());
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment();
+        assert_eq!(contract.get_counter(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "account not whitelisted")]
+    fn test_whitelist_blocks_unlisted_account() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_whitelist_enabled(true);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment();
+    }
+
+    #[test]
+    fn test_whitelist_allows_listed_account_and_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_whitelist_enabled(true);
+        contract.add_to_whitelist(accounts(1));
+        assert!(contract.is_whitelisted(accounts(1)));
+
+        // Owner is always allowed
+        contract.increment();
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment_by(2);
+        contract.decrement();
+        assert_eq!(contract.get_counter(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "account not whitelisted")]
+    fn test_whitelist_removal_takes_effect() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_whitelist_enabled(true);
+        contract.add_to_whitelist(accounts(1));
+        contract.remove_from_whitelist(accounts(1));
+        assert!(!contract.is_whitelisted(accounts(1)));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.decrement();
+    }
+
+    #[test]
+    fn test_events_include_block_height_and_timestamp() {
+        let mut context = get_context(accounts(0));
+        context.block_height(1234);
+        context.block_timestamp(5_000_000);
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.pause();
+
+        let logs = get_logs();
+        assert!(logs[0].contains("\"block_height\":1234"));
+        assert!(logs[0].contains("\"timestamp\":5000000"));
+    }
+
+    #[test]
+    fn test_clear_events_releases_storage() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        for _ in 0..5 {
+            contract.increment();
+        }
+        let populated = contract.storage_usage();
+
+        contract.clear_events();
+        assert!(contract.storage_usage() < populated);
+        assert_eq!(contract.get_events_count(), 0);
+
+        // Refund transfer is issued to the owner
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow error")]
+    fn test_overflow_panics_by_default() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(i64::MAX);
+        assert_eq!(contract.get_overflow_mode(), OverflowMode::Panic);
+        contract.increment();
+    }
+
+    #[test]
+    fn test_saturate_mode_clamps() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(i64::MAX - 1);
+        contract.set_overflow_mode(OverflowMode::Saturate);
+
+        assert_eq!(contract.increment_by(10), i64::MAX);
+        assert!(contract
+            .get_recent_events()
+            .last()
+            .unwrap()
+            .contains("\"saturated\":true"));
+        assert_eq!(contract.increment(), i64::MAX);
+
+        contract.set_counter(i64::MIN + 1);
+        assert_eq!(contract.decrement_by(5), i64::MIN);
+        assert_eq!(contract.decrement(), i64::MIN);
+    }
+
+    #[test]
+    fn test_saturate_mode_reports_unclamped() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_overflow_mode(OverflowMode::Saturate);
+        contract.increment();
+        assert!(contract
+            .get_recent_events()
+            .last()
+            .unwrap()
+            .contains("\"saturated\":false"));
+    }
+
+    #[test]
+    fn test_increment_many() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        assert_eq!(contract.increment_many(25), 35);
+        assert_eq!(contract.get_total_increments(), 25);
+        assert_eq!(contract.get_user_increments(accounts(0)), 25);
+        assert_eq!(contract.get_events_count(), 1);
+    }
+
+    #[test]
+    fn test_increment_many_zero_is_noop() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        assert_eq!(contract.increment_many(0), 10);
+        assert_eq!(contract.get_total_increments(), 0);
+        assert_eq!(contract.get_events_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "batch too large")]
+    fn test_increment_many_too_large() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment_many(MAX_BATCH_INCREMENTS + 1);
+    }
+
+    #[test]
+    fn test_events_round_trip_through_serde_json() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment();
+        contract.decrement_by(3);
+        contract.set_bounds(Some(-10), None);
+        contract.propose_owner(accounts(1));
+        contract.create_counter("a \"quoted\" name".to_string());
+        contract.on_increment_remote_complete(accounts(2), Err(PromiseError::Failed));
+        contract.reset();
+
+        let expected = [
+            "increment",
+            "decrement_by",
+            "set_bounds",
+            "owner_proposed",
+            "create_counter",
+            "increment_remote",
+            "reset",
+        ];
+        let logs = get_logs();
+        assert_eq!(logs.len(), expected.len());
+        for (log, name) in logs.iter().zip(expected) {
+            let payload = log.strip_prefix("EVENT_JSON:").unwrap();
+            let event: Value = serde_json::from_str(payload).unwrap();
+            assert_eq!(event["standard"], "counter");
+            assert_eq!(event["version"], "1.0.0");
+            assert_eq!(event["event"], name);
+            assert!(event["data"][0]["block_height"].is_u64());
+            assert!(event["data"][0]["timestamp"].is_u64());
+        }
+
+        let bounds: Value = serde_json::from_str(&contract.get_all_events()[2]).unwrap();
+        assert_eq!(bounds["data"][0]["min"], -10);
+        assert!(bounds["data"][0]["max"].is_null());
+        let named: Value = serde_json::from_str(&contract.get_all_events()[4]).unwrap();
+        assert_eq!(named["data"][0]["name"], "a \"quoted\" name");
+        let remote: Value = serde_json::from_str(&contract.get_all_events()[5]).unwrap();
+        assert!(remote["data"][0].get("value").is_none());
+    }
+
+    #[test]
+    fn test_participants_are_distinct() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment();
+        contract.increment();
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment();
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.increment_many(3);
+
+        assert_eq!(contract.get_participant_count(), 3);
+        assert_eq!(
+            contract.get_participants(0, 10),
+            vec![accounts(0), accounts(1), accounts(2)]
+        );
+        assert_eq!(contract.get_participants(1, 1), vec![accounts(1)]);
+        assert!(contract.get_participants(3, 10).is_empty());
+    }
+
+    #[test]
+    fn test_reset_to_configured_value() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(7);
+        contract.set_reset_value(42);
+        assert_eq!(contract.get_reset_value(), 42);
+        assert_eq!(contract.reset(), 42);
+        assert_eq!(contract.get_counter(), 42);
+        assert!(contract
+            .get_recent_events()
+            .last()
+            .unwrap()
+            .contains("\"reset_value\":42"));
+    }
+
+    #[test]
+    fn test_reset_value_defaults_to_zero() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(7);
+        assert_eq!(contract.get_reset_value(), 0);
+        assert_eq!(contract.reset(), 0);
+    }
+
+    #[test]
+    fn test_recent_events_window() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        for _ in 0..5 {
+            contract.increment();
+        }
+
+        let all = contract.get_all_events();
+        assert!(contract.get_recent_events_n(0).is_empty());
+        assert_eq!(contract.get_recent_events_n(5), all);
+        assert_eq!(contract.get_recent_events_n(MAX_RECENT_EVENTS), all);
+
+        let window = contract.get_recent_events_n(2);
+        assert_eq!(window, all[3..].to_vec());
+        assert!(window[0].contains("\"value\":4"));
+        assert!(window[1].contains("\"value\":5"));
+    }
+
+    #[test]
+    #[should_panic(expected = "window too large")]
+    fn test_recent_events_window_too_large() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let contract = Counter::new(0);
+        contract.get_recent_events_n(MAX_RECENT_EVENTS + 1);
+    }
+
+    #[test]
+    fn test_events_by_kind() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment();
+        contract.decrement();
+        contract.increment();
+        contract.reset();
+        contract.increment();
+
+        let increments = contract.get_events_by_kind("increment".to_string(), 0, 10);
+        assert_eq!(increments.len(), 3);
+        for event in &increments {
+            let parsed: Value = serde_json::from_str(event).unwrap();
+            assert_eq!(parsed["event"], "increment");
+        }
+        assert_eq!(
+            contract
+                .get_events_by_kind("decrement".to_string(), 0, 10)
+                .len(),
+            1
+        );
+        assert_eq!(
+            contract
+                .get_events_by_kind("reset".to_string(), 0, 10)
+                .len(),
+            1
+        );
+        assert!(contract
+            .get_events_by_kind("set_counter".to_string(), 0, 10)
+            .is_empty());
+
+        let page = contract.get_events_by_kind("increment".to_string(), 1, 1);
+        assert_eq!(page, increments[1..2].to_vec());
+        assert!(contract
+            .get_events_by_kind("increment".to_string(), 3, 10)
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "limit too large")]
+    fn test_events_by_kind_limit_too_large() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let contract = Counter::new(0);
+        contract.get_events_by_kind("increment".to_string(), 0, MAX_EVENTS_BY_KIND + 1);
+    }
+
+    #[test]
+    fn test_multiply_by() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(6);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert_eq!(contract.multiply_by(3), 18);
+        assert_eq!(contract.get_counter(), 18);
+        assert!(contract
+            .get_recent_events()
+            .last()
+            .unwrap()
+            .contains("\"event\":\"multiply_by\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "factor must be positive")]
+    fn test_multiply_by_zero_rejected() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(6);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.multiply_by(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "factor must be positive")]
+    fn test_multiply_by_negative_rejected() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(6);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.multiply_by(-1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Overflow error")]
+    fn test_multiply_by_overflow() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(i64::MAX / 2 + 1);
+        contract.multiply_by(2);
+    }
+
+    #[test]
+    fn test_divide_by_truncates() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(7);
+        assert_eq!(contract.divide_by(2), 3);
+        assert_eq!(contract.divide_by(-2), -1);
+        assert!(contract
+            .get_recent_events()
+            .last()
+            .unwrap()
+            .contains("\"event\":\"divide_by\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero")]
+    fn test_divide_by_zero() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(7);
+        contract.divide_by(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_divide_by_owner_only() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(7);
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.divide_by(2);
+    }
+
+    #[test]
+    fn test_increment_if_matches() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(4);
+        assert_eq!(contract.increment_if(4), 5);
+        assert_eq!(contract.increment_if(5), 6);
+        assert_eq!(contract.get_total_increments(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "value changed")]
+    fn test_increment_if_stale() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(4);
+        contract.increment();
+        contract.increment_if(4);
+    }
+
+    #[test]
+    fn test_decrement_open_by_default() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        assert!(!contract.is_decrement_owner_only());
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert_eq!(contract.decrement(), 9);
+        assert_eq!(contract.decrement_by(4), 5);
+    }
+
+    #[test]
+    fn test_decrement_owner_only_allows_owner() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.set_decrement_owner_only(true);
+        assert!(contract.is_decrement_owner_only());
+        assert_eq!(contract.decrement(), 9);
+
+        // Increments stay public
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert_eq!(contract.increment(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_decrement_owner_only_rejects_others() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.set_decrement_owner_only(true);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.decrement_by(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_decrement_owner_only_rejects_negative_increment_by() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(10);
+        contract.set_decrement_owner_only(true);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert_eq!(contract.increment_by(2), 12);
+        contract.increment_by(-1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_decrement_owner_only_rejects_lowering_multiply_by() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(-5);
+        contract.set_decrement_owner_only(true);
+        assert_eq!(contract.multiply_by(2), -10);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.multiply_by(2);
+    }
+
+    #[test]
+    fn test_persist_events_off_skips_log() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert!(contract.is_persist_events());
+        contract.increment();
+        contract.set_persist_events(false);
+        assert!(!contract.is_persist_events());
+        let persisted = contract.get_events_count();
+
+        contract.increment();
+        contract.decrement_by(5);
+        assert_eq!(contract.get_counter(), -3);
+        assert_eq!(contract.get_events_count(), persisted);
+        // Events are still emitted to the transaction logs
+        assert_eq!(get_logs().len(), 4);
+
+        contract.set_persist_events(true);
+        contract.increment();
+        assert_eq!(contract.get_events_count(), persisted + 2);
+    }
+
+    #[test]
+    fn test_version_matches_crate() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let contract = Counter::new(0);
+        assert_eq!(contract.version(), env!("CARGO_PKG_VERSION"));
+        assert_eq!(contract.version(), "0.2.0");
+    }
+
+    #[test]
+    fn test_multisig_set_counter_two_of_three() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(5);
+        contract.set_multisig(vec![accounts(1), accounts(2), accounts(3)], 2);
+        assert_eq!(contract.get_threshold(), 2);
+        assert_eq!(contract.get_owners().len(), 3);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let id = contract.propose_action(ActionKind::SetCounter, 42);
+        assert!(!contract.confirm_action(id));
+        assert_eq!(contract.get_counter(), 5);
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        assert!(contract.confirm_action(id));
+        assert_eq!(contract.get_counter(), 42);
+
+        let proposal = contract.get_proposal(id).unwrap();
+        assert!(proposal.executed);
+        assert_eq!(proposal.confirmations, vec![accounts(1), accounts(2)]);
+    }
+
+    #[test]
+    fn test_multisig_reset() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(5);
+        contract.set_multisig(vec![accounts(1)], 1);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let id = contract.propose_action(ActionKind::Reset, 0);
+        assert!(contract.confirm_action(id));
+        assert_eq!(contract.get_counter(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "already confirmed")]
+    fn test_multisig_duplicate_confirmation() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_multisig(vec![accounts(1), accounts(2)], 2);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let id = contract.propose_action(ActionKind::SetCounter, 1);
+        contract.confirm_action(id);
+        contract.confirm_action(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "multisig enabled, use propose_action")]
+    fn test_multisig_blocks_direct_set_counter() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_multisig(vec![accounts(0), accounts(1)], 2);
+        contract.set_counter(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only multisig owners can call this method")]
+    fn test_multisig_non_owner_cannot_confirm() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_multisig(vec![accounts(1), accounts(2)], 2);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let id = contract.propose_action(ActionKind::SetCounter, 1);
+
+        context.predecessor_account_id(accounts(3));
+        testing_env!(context.build());
+        contract.confirm_action(id);
+    }
+
+    #[test]
+    #[should_panic(expected = "threshold exceeds owner count")]
+    fn test_multisig_threshold_too_high() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_multisig(vec![accounts(1)], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "multisig enabled, use propose_set_multisig")]
+    fn test_multisig_owner_cannot_disable_alone() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_multisig(vec![accounts(0), accounts(1)], 2);
+        contract.set_multisig(vec![], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "multisig enabled, use propose_action")]
+    fn test_multisig_blocks_undo() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_counter(7);
+        contract.set_multisig(vec![accounts(0), accounts(1)], 2);
+        contract.undo();
+    }
+
+    #[test]
+    fn test_multisig_change_needs_confirmations() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_multisig(vec![accounts(0), accounts(1)], 2);
+
+        let id = contract.propose_set_multisig(vec![], 0);
+        assert!(!contract.confirm_action(id));
+        assert_eq!(contract.get_threshold(), 2);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        assert!(contract.confirm_action(id));
+        assert_eq!(contract.get_threshold(), 0);
+        assert!(contract.get_owners().is_empty());
+
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        assert_eq!(contract.set_counter(3), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "use propose_set_multisig")]
+    fn test_propose_action_rejects_set_multisig() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_multisig(vec![accounts(0)], 1);
+        contract.propose_action(ActionKind::SetMultisig, 0);
+    }
+
+    #[test]
+    fn test_history_records_mutations_in_order() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert!(contract.get_history(0, 10).is_empty());
+
+        context.block_timestamp(100);
+        testing_env!(context.build());
+        contract.increment();
+
+        context.block_timestamp(200);
+        testing_env!(context.build());
+        contract.increment_by(5);
+
+        context.block_timestamp(300);
+        testing_env!(context.build());
+        contract.set_counter(-2);
+
+        context.block_timestamp(400);
+        testing_env!(context.build());
+        contract.undo();
+
+        assert_eq!(
+            contract.get_history(0, 10),
+            vec![(100, 1), (200, 6), (300, -2), (400, 6)]
+        );
+        assert_eq!(contract.get_history(1, 2), vec![(200, 6), (300, -2)]);
+        assert!(contract.get_history(10, 2).is_empty());
+    }
+
+    #[test]
+    fn test_history_capped() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_max_history(3);
+        for i in 1..=5 {
+            context.block_timestamp(i * 10);
+            testing_env!(context.build());
+            contract.increment();
+        }
+        assert_eq!(contract.get_history(0, 10), vec![(30, 3), (40, 4), (50, 5)]);
+
+        contract.set_max_history(2);
+        assert_eq!(contract.get_max_history(), 2);
+        assert_eq!(contract.get_history(0, 10), vec![(40, 4), (50, 5)]);
+    }
+
+    #[test]
+    fn test_get_user_increments_batch() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.increment();
+        contract.increment();
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.increment();
+
+        assert_eq!(
+            contract.get_user_increments_batch(vec![accounts(2), accounts(1), accounts(0)]),
+            vec![1, 0, 2]
+        );
+        assert!(contract.get_user_increments_batch(vec![]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "too many accounts")]
+    fn test_get_user_increments_batch_too_many() {
+        let context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let contract = Counter::new(0);
+        contract.get_user_increments_batch(vec![accounts(1); 101]);
+    }
+
+    #[test]
+    fn test_withdraw_transfers_to_owner() {
+        let mut context = get_context(accounts(0));
+        context.account_balance(NearToken::from_near(10));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        let amount = NearToken::from_near(5);
+        let _ = contract.withdraw(U128(amount.as_yoctonear()));
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(0));
+        assert!(matches!(
+            receipts[0].actions[..],
+            [near_sdk::mock::MockAction::Transfer { deposit, .. }] if deposit == amount
+        ));
+        assert!(contract.get_recent_events()[0].contains("\"event\":\"withdraw\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "amount exceeds withdrawable balance")]
+    fn test_withdraw_more_than_available() {
+        let mut context = get_context(accounts(0));
+        context.account_balance(NearToken::from_near(10));
+        testing_env!(context.build());
+
+        // The storage reserve makes the full balance unavailable
+        let mut contract = Counter::new(0);
+        let _ = contract.withdraw(U128(NearToken::from_near(10).as_yoctonear()));
+    }
+
+    #[test]
+    fn test_withdraw_exact_maximum_with_persisted_events() {
+        let mut context = get_context(accounts(0));
+        context.account_balance(NearToken::from_near(10));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert!(contract.is_persist_events());
+
+        // A first withdraw with the same digit count measures the storage a
+        // persisted withdraw event adds
+        let before = env::storage_usage();
+        let _ = contract.withdraw(U128(NearToken::from_near(1).as_yoctonear()));
+        let event_bytes = env::storage_usage() - before;
+        assert!(event_bytes > 0);
+
+        // The mocked runtime deducts transfers from the balance right away
+        let reserve =
+            env::storage_byte_cost().saturating_mul((env::storage_usage() + event_bytes) as u128);
+        let max = env::account_balance().saturating_sub(reserve);
+        let _ = contract.withdraw(U128(max.as_yoctonear()));
+
+        assert_eq!(get_created_receipts().len(), 2);
+        assert_eq!(
+            env::account_balance(),
+            env::storage_byte_cost().saturating_mul(env::storage_usage() as u128)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "amount exceeds withdrawable balance")]
+    fn test_withdraw_reserves_storage_of_its_own_event() {
+        let mut context = get_context(accounts(0));
+        let balance = NearToken::from_near(10);
+        context.account_balance(balance);
+        testing_env!(context.build());
+
+        // The maximum before the event is persisted no longer covers the
+        // storage the event adds
+        let mut contract = Counter::new(0);
+        let reserve = env::storage_byte_cost().saturating_mul(env::storage_usage() as u128);
+        let _ = contract.withdraw(U128(balance.saturating_sub(reserve).as_yoctonear()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner can call this method")]
+    fn test_withdraw_not_owner() {
+        let mut context = get_context(accounts(0));
+        context.account_balance(NearToken::from_near(10));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        let _ = contract.withdraw(U128(1));
+    }
+
+    #[test]
+    fn test_increment_paid_exact_deposit() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        let fee = NearToken::from_millinear(10);
+        contract.set_min_increment_deposit(U128(fee.as_yoctonear()));
+        assert_eq!(
+            contract.get_min_increment_deposit(),
+            U128(fee.as_yoctonear())
+        );
+
+        context.predecessor_account_id(accounts(1));
+        context.attached_deposit(fee);
+        testing_env!(context.build());
+        assert_eq!(contract.increment_paid(), 1);
+        assert_eq!(contract.get_user_increments(accounts(1)), 1);
+        assert!(get_created_receipts().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "insufficient deposit")]
+    fn test_increment_paid_insufficient_deposit() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_min_increment_deposit(U128(NearToken::from_millinear(10).as_yoctonear()));
+
+        context.attached_deposit(NearToken::from_millinear(9));
+        testing_env!(context.build());
+        contract.increment_paid();
+    }
+
+    #[test]
+    fn test_increment_paid_refunds_excess() {
+        let mut context = get_context(accounts(0));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        contract.set_min_increment_deposit(U128(NearToken::from_millinear(10).as_yoctonear()));
+
+        context.predecessor_account_id(accounts(1));
+        context.attached_deposit(NearToken::from_millinear(25));
+        testing_env!(context.build());
+        assert_eq!(contract.increment_paid(), 1);
+
+        let receipts = get_created_receipts();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].receiver_id, accounts(1));
+        assert!(matches!(
+            receipts[0].actions[..],
+            [near_sdk::mock::MockAction::Transfer { deposit, .. }]
+                if deposit == NearToken::from_millinear(15)
+        ));
+    }
+
+    #[test]
+    fn test_top_contributor_flips_when_overtaken() {
+        let mut context = get_context(accounts(1));
+        testing_env!(context.build());
+
+        let mut contract = Counter::new(0);
+        assert_eq!(contract.get_top_contributor(), None);
+        contract.increment();
+        contract.increment();
+        assert_eq!(contract.get_top_contributor(), Some((accounts(1), 2)));
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.increment();
+        contract.increment();
+        // A tie does not take the lead
+        assert_eq!(contract.get_top_contributor(), Some((accounts(1), 2)));
+
+        contract.increment_many(2);
+        assert_eq!(contract.get_top_contributor(), Some((accounts(2), 4)));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.increment();
+        assert_eq!(contract.get_top_contributor(), Some((accounts(2), 4)));
     }
 }
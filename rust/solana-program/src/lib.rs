@@ -1,76 +1,470 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+use counter_core::{CounterError, CounterState};
 
 // Program ID (will be generated after deployment)
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
+/// Semantic version of this program build
+///
+/// Taken from the crate version; bump it whenever the account layout changes.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Seed prefix for per-user counter PDAs
+pub const COUNTER_SEED: &[u8] = b"counter";
+
+/// Seed of the global leaderboard PDA
+pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+
+/// Number of entries kept on the leaderboard
+pub const LEADERBOARD_LEN: usize = 10;
+
+/// Number of recent values kept in the on-chain history ring
+pub const HISTORY_LEN: usize = 8;
+
+/// Maximum length of a counter name in bytes
+pub const MAX_NAME_LEN: usize = 32;
+
 /// Counter Program - A simple Solana program demonstrating basic operations
 #[program]
 pub mod counter_program {
     use super::*;
 
     /// Initialize a new counter account
+    ///
+    /// Idempotent: calling it again on an existing counter is a no-op, so
+    /// clients racing to create the same counter do not fail.
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let counter = &mut ctx.accounts.counter;
-        counter.count = 0;
-        counter.authority = *ctx.accounts.user.key;
+        let info = ctx.accounts.counter.to_account_info();
+        if Counter::exists(&info)? {
+            msg!("Counter already initialized");
+            return Ok(());
+        }
+        ctx.accounts.create_counter()?;
+
+        let mut data = info.try_borrow_mut_data()?;
+        let mut counter = Counter::try_deserialize_unchecked(&mut &data[..])?;
+        counter.init_once(*ctx.accounts.user.key);
+        counter.touch(&Clock::get()?);
+        counter.try_serialize(&mut &mut data[..])?;
         msg!("Counter initialized to 0");
         Ok(())
     }
 
-    /// Increment the counter
+    /// Initialize the caller's counter at a PDA derived from their key
+    pub fn initialize_pda(ctx: Context<InitializePda>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.set_inner(Counter {
+            authority: *ctx.accounts.user.key,
+            bump: ctx.bumps.counter,
+            creator: *ctx.accounts.user.key,
+            ..Default::default()
+        });
+        counter.touch(&Clock::get()?);
+        msg!("PDA counter initialized to 0 with bump {}", counter.bump);
+        Ok(())
+    }
+
+    /// Increment the counter by its step
+    ///
+    /// Like every count-changing instruction except `reset`, this returns the
+    /// new count as 8 little-endian bytes via return data. Passing the
+    /// optional leaderboard re-ranks a PDA counter, as does `increment_by`.
     pub fn increment(ctx: Context<Update>) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
+        counter.check_not_paused()?;
+        counter.check_not_frozen()?;
+        let old = counter.count;
+        let step = counter.step;
         counter.count = counter
-            .count
-            .checked_add(1)
-            .ok_or(ErrorCode::Overflow)?;
+            .state()
+            .increment_by(step)
+            .map_err(ErrorCode::from)?;
+        counter.add_increments(step);
+        counter.record_history();
+        counter.touch(&Clock::get()?);
+        emit!(counter.changed(counter.key(), old));
         msg!("Counter incremented to {}", counter.count);
+        counter.publish_count();
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record_counter(counter);
+        }
         Ok(())
     }
 
-    /// Decrement the counter
+    /// Decrement the counter by its step
     pub fn decrement(ctx: Context<Update>) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
-        counter.count = counter
-            .count
-            .checked_sub(1)
-            .ok_or(ErrorCode::Underflow)?;
+        counter.check_not_paused()?;
+        counter.check_not_frozen()?;
+        let old = counter.count;
+        let count = counter
+            .state()
+            .decrement_by(counter.step)
+            .map_err(ErrorCode::from)?;
+        counter.check_floor(count)?;
+        counter.count = count;
+        counter.record_history();
+        counter.touch(&Clock::get()?);
+        emit!(counter.changed(counter.key(), old));
         msg!("Counter decremented to {}", counter.count);
+        counter.publish_count();
+        Ok(())
+    }
+
+    /// Increment the counter by a custom amount
+    pub fn increment_by(ctx: Context<Update>, amount: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_not_paused()?;
+        counter.check_not_frozen()?;
+        let old = counter.count;
+        counter.count = counter
+            .state()
+            .increment_by(amount)
+            .map_err(ErrorCode::from)?;
+        counter.add_increments(amount);
+        counter.record_history();
+        counter.touch(&Clock::get()?);
+        emit!(counter.changed(counter.key(), old));
+        msg!("Counter incremented by {} to {}", amount, counter.count);
+        counter.publish_count();
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record_counter(counter);
+        }
+        Ok(())
+    }
+
+    /// Decrement the counter by a custom amount
+    pub fn decrement_by(ctx: Context<Update>, amount: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_not_paused()?;
+        counter.check_not_frozen()?;
+        let old = counter.count;
+        let count = counter
+            .state()
+            .decrement_by(amount)
+            .map_err(ErrorCode::from)?;
+        counter.check_floor(count)?;
+        counter.count = count;
+        counter.record_history();
+        counter.touch(&Clock::get()?);
+        emit!(counter.changed(counter.key(), old));
+        msg!("Counter decremented by {} to {}", amount, counter.count);
+        counter.publish_count();
         Ok(())
     }
 
-    /// Set counter to a specific value (only authority)
+    /// Set counter to a specific value within the bounds (only authority)
     pub fn set(ctx: Context<Update>, value: u64) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
-        require!(
-            ctx.accounts.user.key() == counter.authority,
-            ErrorCode::Unauthorized
-        );
-        counter.count = value;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.check_not_paused()?;
+        let old = counter.count;
+        counter.count = counter.state().set(value).map_err(ErrorCode::from)?;
+        counter.record_history();
+        counter.touch(&Clock::get()?);
+        emit!(counter.changed(counter.key(), old));
         msg!("Counter set to {}", value);
+        counter.publish_count();
         Ok(())
     }
 
-    /// Reset the counter to zero (only authority)
+    /// Compare-and-swap variant of `set` (only authority)
+    ///
+    /// Fails with `StaleValue` unless the count still equals `expected_current`,
+    /// so concurrent clients cannot silently overwrite each other.
+    pub fn set_checked(ctx: Context<Update>, value: u64, expected_current: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.check_not_paused()?;
+        counter.check_current(expected_current)?;
+        let old = counter.count;
+        counter.count = counter.state().set(value).map_err(ErrorCode::from)?;
+        counter.record_history();
+        counter.touch(&Clock::get()?);
+        emit!(counter.changed(counter.key(), old));
+        msg!("Counter set from {} to {}", old, value);
+        counter.publish_count();
+        Ok(())
+    }
+
+    /// Reset the counter to zero (authority or delegate, allowed while paused)
     pub fn reset(ctx: Context<Update>) -> Result<()> {
         let counter = &mut ctx.accounts.counter;
-        require!(
-            ctx.accounts.user.key() == counter.authority,
-            ErrorCode::Unauthorized
-        );
+        counter.check_authority_or_delegate(&ctx.accounts.user.key())?;
+        let old = counter.count;
         counter.count = 0;
+        counter.record_history();
+        counter.touch(&Clock::get()?);
+        emit!(counter.changed(counter.key(), old));
         msg!("Counter reset to 0");
         Ok(())
     }
+
+    /// Set the operator key allowed to reset (only authority)
+    ///
+    /// Passing the default key removes the delegate.
+    pub fn set_delegate(ctx: Context<Update>, delegate: Pubkey) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.delegate = delegate;
+        msg!("Delegate set to {}", delegate);
+        Ok(())
+    }
+
+    /// Change the increment/decrement step (only authority)
+    pub fn set_step(ctx: Context<Update>, step: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.update_step(step)?;
+        msg!("Counter step set to {}", step);
+        Ok(())
+    }
+
+    /// Pause counter mutations (only authority)
+    pub fn pause(ctx: Context<Update>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.paused = true;
+        msg!("Counter paused");
+        Ok(())
+    }
+
+    /// Resume counter mutations (only authority)
+    pub fn unpause(ctx: Context<Update>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.paused = false;
+        msg!("Counter unpaused");
+        Ok(())
+    }
+
+    /// Freeze this counter against public changes (only authority)
+    ///
+    /// Unlike `pause`, `set` keeps working so the authority can still correct it.
+    pub fn freeze(ctx: Context<Update>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.frozen = true;
+        msg!("Counter frozen");
+        Ok(())
+    }
+
+    /// Lift a freeze (only authority)
+    pub fn thaw(ctx: Context<Update>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.frozen = false;
+        msg!("Counter thawed");
+        Ok(())
+    }
+
+    /// Constrain the counter to `[min, max]` (only authority)
+    pub fn set_bounds(ctx: Context<Update>, min: u64, max: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        require!(min <= max, ErrorCode::InvalidBounds);
+        counter.min_bound = min;
+        counter.max_bound = max;
+        msg!("Counter bounds set to [{}, {}]", min, max);
+        Ok(())
+    }
+
+    /// Set the floor that decrements may not go below (only authority)
+    ///
+    /// Unlike `min_bound`, this only restricts decrements; `set` and `reset`
+    /// are unaffected.
+    pub fn set_floor(ctx: Context<Update>, floor: u64) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.check_authority(&ctx.accounts.user.key())?;
+        counter.floor = floor;
+        msg!("Counter floor set to {}", floor);
+        Ok(())
+    }
+
+    /// Transfer authority to a new key (only authority)
+    pub fn set_authority(ctx: Context<Update>, new_authority: Pubkey) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        let old_authority = counter.authority;
+        counter.transfer_authority(&ctx.accounts.user.key(), new_authority)?;
+        msg!(
+            "Authority changed from {} to {}",
+            old_authority,
+            new_authority
+        );
+        Ok(())
+    }
+
+    /// Give up authority for good, making the counter's settings immutable
+    /// (only authority)
+    ///
+    /// The authority becomes the all-zeros key, which can never sign, and the
+    /// delegate and any pending proposal are cleared with it.
+    pub fn renounce_authority(ctx: Context<Update>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.renounce_authority(&ctx.accounts.user.key())?;
+        msg!("Authority renounced");
+        Ok(())
+    }
+
+    /// Propose a new authority; it takes effect once accepted (only authority)
+    ///
+    /// Proposing the default key cancels any pending proposal.
+    pub fn propose_authority(ctx: Context<Update>, new_authority: Pubkey) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        counter.propose_authority(&ctx.accounts.user.key(), new_authority)?;
+        msg!("Authority {} proposed", new_authority);
+        Ok(())
+    }
+
+    /// Accept a pending authority proposal (only the proposed key)
+    pub fn accept_authority(ctx: Context<Update>) -> Result<()> {
+        let counter = &mut ctx.accounts.counter;
+        let old_authority = counter.authority;
+        counter.accept_authority(&ctx.accounts.user.key())?;
+        msg!(
+            "Authority changed from {} to {}",
+            old_authority,
+            counter.authority
+        );
+        Ok(())
+    }
+
+    /// Grow the counter account and store a UTF-8 name (only authority)
+    pub fn set_name(ctx: Context<SetName>, name: String) -> Result<()> {
+        ctx.accounts
+            .counter
+            .check_authority(&ctx.accounts.user.key())?;
+        let info = ctx.accounts.counter.to_account_info();
+        Counter::write_name(&mut info.try_borrow_mut_data()?, &name)?;
+        msg!("Counter name set to {}", name);
+        Ok(())
+    }
+
+    /// Create the global leaderboard PDA
+    pub fn initialize_leaderboard(ctx: Context<InitializeLeaderboard>) -> Result<()> {
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.bump = ctx.bumps.leaderboard;
+        msg!("Leaderboard initialized with bump {}", leaderboard.bump);
+        Ok(())
+    }
+
+    /// Submit a per-user PDA counter's increment total to the leaderboard
+    ///
+    /// Permissionless and CPI-friendly: the board ranks `total_increments`,
+    /// which only `increment`/`increment_by` raise, so neither callers nor
+    /// the authority's `set` can inflate it. Increments already re-rank when
+    /// given the leaderboard; this submits counters that never passed it.
+    pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>) -> Result<()> {
+        let counter = &ctx.accounts.counter;
+        let ranked = ctx.accounts.leaderboard.record_counter(counter);
+        msg!(
+            "Leaderboard {} {} at {}",
+            if ranked { "ranks" } else { "skips" },
+            counter.creator,
+            counter.total_increments
+        );
+        Ok(())
+    }
+
+    /// Report the program version as UTF-8 return data
+    pub fn version(_ctx: Context<GetVersion>) -> Result<()> {
+        set_return_data(VERSION.as_bytes());
+        msg!("Counter program v{}", VERSION);
+        Ok(())
+    }
+
+    /// Close the counter and return its rent to the authority (only authority)
+    pub fn close(ctx: Context<Close>) -> Result<()> {
+        ctx.accounts
+            .counter
+            .check_authority(&ctx.accounts.user.key())?;
+        msg!("Counter closed");
+        Ok(())
+    }
+}
+
+/// Emitted whenever the count changes
+#[event]
+pub struct CounterChanged {
+    pub counter: Pubkey,
+    pub authority: Pubkey,
+    pub old: u64,
+    pub new: u64,
 }
 
-/// Context for initializing the counter
+/// Context for initializing the counter, reusing it if it already exists
+///
+/// The account is created by hand rather than with `init_if_needed`, whose
+/// size check rejects counters that `set_name` has grown.
 #[derive(Accounts)]
 pub struct Initialize<'info> {
+    /// CHECK: created by `initialize` while owned by the system program,
+    /// otherwise validated by `Counter::exists`
+    #[account(mut)]
+    pub counter: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    /// Create the counter account at its unnamed size, owned by this program
+    ///
+    /// Mirrors Anchor's `init`: an account already holding lamports is topped
+    /// up to rent exemption, then allocated and assigned.
+    fn create_counter(&self) -> Result<()> {
+        let space = 8 + Counter::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space);
+        let system_program = self.system_program.to_account_info();
+        let counter = self.counter.to_account_info();
+        let user = self.user.to_account_info();
+
+        let lamports = counter.lamports();
+        if lamports == 0 {
+            let accounts = CreateAccount {
+                from: user,
+                to: counter,
+            };
+            return create_account(
+                CpiContext::new(system_program, accounts),
+                rent,
+                space as u64,
+                &crate::ID,
+            );
+        }
+
+        let shortfall = rent.saturating_sub(lamports);
+        if shortfall > 0 {
+            let accounts = Transfer {
+                from: user,
+                to: counter.clone(),
+            };
+            transfer(CpiContext::new(system_program.clone(), accounts), shortfall)?;
+        }
+        let accounts = Allocate {
+            account_to_allocate: counter.clone(),
+        };
+        allocate(CpiContext::new(system_program.clone(), accounts), space as u64)?;
+        let accounts = Assign {
+            account_to_assign: counter,
+        };
+        assign(CpiContext::new(system_program, accounts), &crate::ID)
+    }
+}
+
+/// Context for initializing a per-user counter PDA
+#[derive(Accounts)]
+pub struct InitializePda<'info> {
     #[account(
         init,
         payer = user,
-        space = 8 + Counter::INIT_SPACE
+        space = 8 + Counter::INIT_SPACE,
+        seeds = [COUNTER_SEED, user.key().as_ref()],
+        bump
     )]
     pub counter: Account<'info, Counter>,
     #[account(mut)]
@@ -79,11 +473,75 @@ pub struct Initialize<'info> {
 }
 
 /// Context for updating the counter
+///
+/// PDA counters are re-derived from their stored creator key and bump, so a
+/// counter at any other address is rejected.
 #[derive(Accounts)]
 pub struct Update<'info> {
+    #[account(mut, constraint = counter.has_address(&counter.key()) @ ErrorCode::InvalidPda)]
+    pub counter: Account<'info, Counter>,
+    pub user: Signer<'info>,
+    /// Leaderboard re-ranked by increments; may be omitted
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump = leaderboard.bump)]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+}
+
+/// Context for creating the global leaderboard
+#[derive(Accounts)]
+pub struct InitializeLeaderboard<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [LEADERBOARD_SEED],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for submitting a per-user PDA counter to the leaderboard
+#[derive(Accounts)]
+pub struct UpdateLeaderboard<'info> {
+    #[account(mut, seeds = [LEADERBOARD_SEED], bump = leaderboard.bump)]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(seeds = [COUNTER_SEED, counter.creator.as_ref()], bump = counter.bump)]
+    pub counter: Account<'info, Counter>,
+}
+
+/// Context for querying the program version; no accounts needed
+#[derive(Accounts)]
+pub struct GetVersion {}
+
+/// Context for closing the counter
+#[derive(Accounts)]
+pub struct Close<'info> {
+    #[account(
+        mut,
+        close = user,
+        constraint = counter.has_address(&counter.key()) @ ErrorCode::InvalidPda
+    )]
+    pub counter: Account<'info, Counter>,
     #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Context for naming the counter, growing the account to fit the name
+#[derive(Accounts)]
+pub struct SetName<'info> {
+    #[account(
+        mut,
+        realloc = 8 + Counter::INIT_SPACE + 4 + MAX_NAME_LEN,
+        realloc::payer = user,
+        realloc::zero = false,
+        constraint = counter.has_address(&counter.key()) @ ErrorCode::InvalidPda
+    )]
     pub counter: Account<'info, Counter>,
+    #[account(mut)]
     pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Counter account structure
@@ -92,6 +550,341 @@ pub struct Update<'info> {
 pub struct Counter {
     pub count: u64,      // Current count value
     pub authority: Pubkey, // Account with special permissions
+    pub bump: u8,        // PDA bump seed (0 for keypair accounts)
+    pub min_bound: u64,  // Lowest allowed count
+    pub max_bound: u64,  // Highest allowed count
+    pub paused: bool,    // Blocks mutations other than reset
+    pub last_updated_ts: i64, // Unix timestamp of the last change
+    pub last_updated_slot: u64, // Slot of the last change
+    pub step: u64,       // Amount applied by increment/decrement
+    pub pending_authority: Pubkey, // Proposed authority (default when none)
+    pub history: [u64; HISTORY_LEN], // Ring of recent post-mutation values
+    pub history_head: u8, // Next history slot to write
+    pub delegate: Pubkey, // Operator key allowed to reset (default when none)
+    pub frozen: bool,    // Blocks public increments/decrements only
+    pub floor: u64,      // Lowest count decrements may reach
+    pub creator: Pubkey, // Key the PDA is derived from (default for keypair accounts)
+    pub total_increments: u64, // Sum added by increment/increment_by, never lowered
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            authority: Pubkey::default(),
+            bump: 0,
+            min_bound: 0,
+            max_bound: u64::MAX,
+            paused: false,
+            last_updated_ts: 0,
+            last_updated_slot: 0,
+            step: 1,
+            pending_authority: Pubkey::default(),
+            history: [0; HISTORY_LEN],
+            history_head: 0,
+            delegate: Pubkey::default(),
+            frozen: false,
+            floor: 0,
+            creator: Pubkey::default(),
+            total_increments: 0,
+        }
+    }
+}
+
+impl Counter {
+    /// Offset of the optional name, stored after the fixed account layout
+    pub const NAME_OFFSET: usize = 8 + Counter::INIT_SPACE;
+    /// Account size once a name has been set
+    pub const NAMED_SPACE: usize = Self::NAME_OFFSET + 4 + MAX_NAME_LEN;
+
+    /// Whether the account holds a counter rather than freshly zeroed data
+    ///
+    /// Keyed on `step`, which is never zero once initialized, rather than on
+    /// `authority`, which may legitimately be the default key.
+    pub fn is_initialized(&self) -> bool {
+        self.step != 0
+    }
+
+    /// Whether `info` already holds a counter, at its initial or named size
+    ///
+    /// System-owned accounts have not been created yet; any other owner must
+    /// be this program with a valid counter discriminator.
+    pub fn exists(info: &AccountInfo) -> Result<bool> {
+        if info.owner == &System::id() {
+            return Ok(false);
+        }
+        if info.owner != &crate::ID {
+            return Err(Error::from(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram)
+                .with_pubkeys((*info.owner, crate::ID)));
+        }
+        Counter::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        Ok(true)
+    }
+
+    /// Initialize a fresh account for `authority`, leaving an existing counter untouched
+    ///
+    /// Returns whether this call performed the initialization.
+    pub fn init_once(&mut self, authority: Pubkey) -> bool {
+        if self.is_initialized() {
+            return false;
+        }
+        *self = Counter {
+            authority,
+            ..Default::default()
+        };
+        true
+    }
+
+    /// Derive the counter PDA for a user
+    pub fn pda(user: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[COUNTER_SEED, user.as_ref()], &crate::ID)
+    }
+
+    /// Add `amount` to the increment total ranked by the leaderboard
+    pub fn add_increments(&mut self, amount: u64) {
+        self.total_increments = self.total_increments.saturating_add(amount);
+    }
+
+    /// Whether this is a per-user PDA counter rather than a keypair account
+    pub fn is_pda(&self) -> bool {
+        self.creator != Pubkey::default()
+    }
+
+    /// Whether `key` is where this counter belongs
+    ///
+    /// PDA counters must sit at the address derived from `creator` and the
+    /// stored bump, which stays valid after the authority changes; keypair
+    /// counters may live anywhere.
+    pub fn has_address(&self, key: &Pubkey) -> bool {
+        if !self.is_pda() {
+            return true;
+        }
+        Pubkey::create_program_address(
+            &[COUNTER_SEED, self.creator.as_ref(), &[self.bump]],
+            &crate::ID,
+        )
+        .is_ok_and(|address| address == *key)
+    }
+
+    /// Build the change event for this counter's current value
+    pub fn changed(&self, counter: Pubkey, old: u64) -> CounterChanged {
+        CounterChanged {
+            counter,
+            authority: self.authority,
+            old,
+            new: self.count,
+        }
+    }
+
+    /// Record a proposed authority for the current authority's signature
+    pub fn propose_authority(&mut self, signer: &Pubkey, new_authority: Pubkey) -> Result<()> {
+        self.check_authority(signer)?;
+        self.pending_authority = new_authority;
+        Ok(())
+    }
+
+    /// Clear the authority, delegate and pending authority for the current
+    /// authority's signature
+    pub fn renounce_authority(&mut self, signer: &Pubkey) -> Result<()> {
+        self.check_authority(signer)?;
+        self.authority = Pubkey::default();
+        self.delegate = Pubkey::default();
+        self.pending_authority = Pubkey::default();
+        Ok(())
+    }
+
+    /// Whether the authority has been renounced
+    pub fn is_renounced(&self) -> bool {
+        self.authority == Pubkey::default()
+    }
+
+    /// Promote the pending authority if it is the signer
+    pub fn accept_authority(&mut self, signer: &Pubkey) -> Result<()> {
+        require_keys_neq!(
+            self.pending_authority,
+            Pubkey::default(),
+            ErrorCode::Unauthorized
+        );
+        require_keys_eq!(*signer, self.pending_authority, ErrorCode::Unauthorized);
+        self.authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+        Ok(())
+    }
+
+    /// Write a length-prefixed name into a grown counter account's data
+    pub fn write_name(data: &mut [u8], name: &str) -> Result<()> {
+        require!(name.len() <= MAX_NAME_LEN, ErrorCode::NameTooLong);
+        let len = name.len() as u32;
+        let slot = &mut data[Self::NAME_OFFSET..Self::NAMED_SPACE];
+        slot[..4].copy_from_slice(&len.to_le_bytes());
+        slot[4..4 + name.len()].copy_from_slice(name.as_bytes());
+        Ok(())
+    }
+
+    /// Read the name from counter account data, if the account has been grown
+    pub fn read_name(data: &[u8]) -> Option<String> {
+        let slot = data.get(Self::NAME_OFFSET..Self::NAMED_SPACE)?;
+        let len = u32::from_le_bytes(slot[..4].try_into().ok()?) as usize;
+        let bytes = slot.get(4..4 + len)?;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+
+    /// Set a new non-zero step
+    pub fn update_step(&mut self, step: u64) -> Result<()> {
+        require!(step > 0, ErrorCode::InvalidStep);
+        self.step = step;
+        Ok(())
+    }
+
+    /// Expose the count as return data, encoded as a little-endian `u64`
+    pub fn publish_count(&self) {
+        set_return_data(&self.count.to_le_bytes());
+    }
+
+    /// Append the current count to the history ring
+    pub fn record_history(&mut self) {
+        self.history[self.history_head as usize] = self.count;
+        self.history_head = ((self.history_head as usize + 1) % HISTORY_LEN) as u8;
+    }
+
+    /// History ordered from oldest to newest
+    ///
+    /// The raw ring is read starting at `history_head` (the oldest entry) and
+    /// wrapping around; slots never written yet read as 0.
+    pub fn recent_history(&self) -> [u64; HISTORY_LEN] {
+        let mut ordered = [0; HISTORY_LEN];
+        for (i, value) in ordered.iter_mut().enumerate() {
+            *value = self.history[(self.history_head as usize + i) % HISTORY_LEN];
+        }
+        ordered
+    }
+
+    /// Record when the counter last changed
+    pub fn touch(&mut self, clock: &Clock) {
+        self.last_updated_ts = clock.unix_timestamp;
+        self.last_updated_slot = clock.slot;
+        msg!(
+            "Counter updated at slot {} (ts {})",
+            clock.slot,
+            clock.unix_timestamp
+        );
+    }
+
+    /// Ensure the counter is not paused
+    pub fn check_not_paused(&self) -> Result<()> {
+        require!(!self.paused, ErrorCode::Paused);
+        Ok(())
+    }
+
+    /// Ensure the count still holds the value the caller last read
+    pub fn check_current(&self, expected: u64) -> Result<()> {
+        require_eq!(self.count, expected, ErrorCode::StaleValue);
+        Ok(())
+    }
+
+    /// Ensure a decrement result does not drop under the floor
+    pub fn check_floor(&self, value: u64) -> Result<()> {
+        require_gte!(value, self.floor, ErrorCode::BelowFloor);
+        Ok(())
+    }
+
+    /// Ensure the counter is not frozen
+    pub fn check_not_frozen(&self) -> Result<()> {
+        require!(!self.frozen, ErrorCode::Frozen);
+        Ok(())
+    }
+
+    /// Shared checked-arithmetic view of the count and its bounds
+    pub fn state(&self) -> CounterState<u64> {
+        CounterState::with_bounds(self.count, Some(self.min_bound), Some(self.max_bound))
+    }
+
+    /// Ensure the count lies within the configured bounds
+    pub fn check_bounds(&self) -> Result<()> {
+        self.state()
+            .check_bounds(self.count)
+            .map_err(ErrorCode::from)?;
+        Ok(())
+    }
+
+    /// Ensure the given signer is the counter authority
+    ///
+    /// Always fails once the authority has been renounced.
+    pub fn check_authority(&self, signer: &Pubkey) -> Result<()> {
+        require!(!self.is_renounced(), ErrorCode::Unauthorized);
+        require_keys_eq!(*signer, self.authority, ErrorCode::Unauthorized);
+        Ok(())
+    }
+
+    /// Ensure the given signer is the authority or the configured delegate
+    pub fn check_authority_or_delegate(&self, signer: &Pubkey) -> Result<()> {
+        if self.delegate != Pubkey::default() && *signer == self.delegate {
+            return Ok(());
+        }
+        self.check_authority(signer)
+    }
+
+    /// Hand authority to a new, non-default key
+    pub fn transfer_authority(&mut self, signer: &Pubkey, new_authority: Pubkey) -> Result<()> {
+        self.check_authority(signer)?;
+        require_keys_neq!(
+            new_authority,
+            Pubkey::default(),
+            ErrorCode::InvalidAuthority
+        );
+        self.authority = new_authority;
+        Ok(())
+    }
+}
+
+/// A user and their counter's count on the leaderboard
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct LeaderboardEntry {
+    pub user: Pubkey,
+    pub count: u64,
+}
+
+/// Global top-`LEADERBOARD_LEN` users by count, highest first
+///
+/// Unused slots hold the default entry and always sort last.
+#[account]
+#[derive(Default, InitSpace)]
+pub struct Leaderboard {
+    pub entries: [LeaderboardEntry; LEADERBOARD_LEN],
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    /// Insert or update `user` with `count`, keeping entries sorted
+    ///
+    /// A new user evicts the lowest entry only with a strictly higher count.
+    /// Returns whether the user is on the leaderboard afterwards.
+    pub fn record(&mut self, user: Pubkey, count: u64) -> bool {
+        if let Some(index) = self.entries.iter().position(|entry| entry.user == user) {
+            self.entries[index..].rotate_left(1);
+            self.entries[LEADERBOARD_LEN - 1] = LeaderboardEntry::default();
+        }
+
+        let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| entry.user == Pubkey::default() || count > entry.count)
+        else {
+            return false;
+        };
+        self.entries[index..].rotate_right(1);
+        self.entries[index] = LeaderboardEntry { user, count };
+        true
+    }
+
+    /// Rank a PDA counter's increment total under its creator, whose key
+    /// never changes
+    ///
+    /// Keypair counters are ignored. Returns whether the counter is on the
+    /// leaderboard afterwards.
+    pub fn record_counter(&mut self, counter: &Counter) -> bool {
+        counter.is_pda() && self.record(counter.creator, counter.total_increments)
+    }
 }
 
 /// Custom error codes
@@ -103,4 +896,34 @@ pub enum ErrorCode {
     Underflow,
     #[msg("Unauthorized access")]
     Unauthorized,
+    #[msg("Invalid authority")]
+    InvalidAuthority,
+    #[msg("Value is outside the configured bounds")]
+    OutOfBounds,
+    #[msg("Min bound must not exceed max bound")]
+    InvalidBounds,
+    #[msg("Counter is paused")]
+    Paused,
+    #[msg("Step must be greater than zero")]
+    InvalidStep,
+    #[msg("Name exceeds 32 bytes")]
+    NameTooLong,
+    #[msg("Counter is frozen")]
+    Frozen,
+    #[msg("Counter changed since it was read")]
+    StaleValue,
+    #[msg("Value would drop below the floor")]
+    BelowFloor,
+    #[msg("Counter is not at its derived PDA address")]
+    InvalidPda,
+}
+
+impl From<CounterError> for ErrorCode {
+    fn from(error: CounterError) -> Self {
+        match error {
+            CounterError::Overflow => ErrorCode::Overflow,
+            CounterError::Underflow => ErrorCode::Underflow,
+            CounterError::BelowMin | CounterError::AboveMax => ErrorCode::OutOfBounds,
+        }
+    }
 }
@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
-use counter_program::{Counter, ErrorCode};
+use anchor_lang::solana_program::program::get_return_data;
+use anchor_lang::solana_program::program_stubs::{self, SyscallStubs};
+use anchor_lang::{Discriminator, Event};
+use counter_program::{
+    Counter, CounterChanged, ErrorCode, Leaderboard, COUNTER_SEED, LEADERBOARD_LEN, MAX_NAME_LEN,
+    VERSION,
+};
+use std::sync::Mutex;
 
 #[cfg(test)]
 mod tests {
@@ -11,6 +18,7 @@ mod tests {
         let counter = Counter {
             count: 0,
             authority: Pubkey::new_unique(),
+            ..Default::default()
         };
 
         assert_eq!(counter.count, 0);
@@ -21,6 +29,7 @@ mod tests {
         let mut counter = Counter {
             count: 0,
             authority: Pubkey::new_unique(),
+            ..Default::default()
         };
 
         // Simulate increment
@@ -34,6 +43,7 @@ mod tests {
         let mut counter = Counter {
             count: 10,
             authority: Pubkey::new_unique(),
+            ..Default::default()
         };
 
         // Simulate decrement
@@ -44,10 +54,12 @@ mod tests {
 
     #[test]
     #[should_panic]
+    #[allow(unused_assignments)]
     fn test_underflow() {
         let mut counter = Counter {
             count: 0,
             authority: Pubkey::new_unique(),
+            ..Default::default()
         };
 
         // This should panic due to underflow
@@ -59,6 +71,7 @@ mod tests {
         let mut counter = Counter {
             count: 5,
             authority: Pubkey::new_unique(),
+            ..Default::default()
         };
 
         counter.count = 100;
@@ -71,10 +84,865 @@ mod tests {
         let mut counter = Counter {
             count: 999,
             authority: Pubkey::new_unique(),
+            ..Default::default()
         };
 
         counter.count = 0;
 
         assert_eq!(counter.count, 0);
     }
+
+    #[test]
+    fn test_increment_by() {
+        let mut counter = Counter {
+            count: 10,
+            authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        // Simulate increment_by
+        counter.count = counter.count.checked_add(25).unwrap();
+
+        assert_eq!(counter.count, 35);
+    }
+
+    #[test]
+    fn test_decrement_by() {
+        let mut counter = Counter {
+            count: 10,
+            authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        // Simulate decrement_by
+        counter.count = counter.count.checked_sub(4).unwrap();
+
+        assert_eq!(counter.count, 6);
+    }
+
+    #[test]
+    fn test_increment_by_overflow() {
+        let counter = Counter {
+            count: u64::MAX - 1,
+            authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        let result = counter.count.checked_add(2).ok_or(ErrorCode::Overflow);
+
+        assert!(matches!(result, Err(ErrorCode::Overflow)));
+    }
+
+    #[test]
+    fn test_decrement_by_underflow() {
+        let counter = Counter {
+            count: 3,
+            authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        // decrement_by(4) fails without touching the stored count
+        let result = counter.state().decrement_by(4).map_err(ErrorCode::from);
+
+        assert!(matches!(result, Err(ErrorCode::Underflow)));
+        assert_eq!(counter.count, 3);
+    }
+
+    #[test]
+    fn test_close_authority_only() {
+        let authority = Pubkey::new_unique();
+        let counter = Counter {
+            count: 7,
+            authority,
+            ..Default::default()
+        };
+
+        assert!(counter.check_authority(&authority).is_ok());
+        assert_eq!(
+            counter.check_authority(&Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::Unauthorized.into()
+        );
+    }
+
+    #[test]
+    fn test_set_authority() {
+        let old_authority = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+        let mut counter = Counter {
+            count: 42,
+            authority: old_authority,
+            ..Default::default()
+        };
+
+        counter
+            .transfer_authority(&old_authority, new_authority)
+            .unwrap();
+        assert_eq!(counter.authority, new_authority);
+
+        // The new authority can now reset, the old one cannot
+        assert!(counter.check_authority(&new_authority).is_ok());
+        assert_eq!(
+            counter.check_authority(&old_authority).unwrap_err(),
+            ErrorCode::Unauthorized.into()
+        );
+    }
+
+    #[test]
+    fn test_set_authority_unauthorized() {
+        let authority = Pubkey::new_unique();
+        let mut counter = Counter {
+            count: 0,
+            authority,
+            ..Default::default()
+        };
+
+        let result = counter.transfer_authority(&Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(result.unwrap_err(), ErrorCode::Unauthorized.into());
+        assert_eq!(counter.authority, authority);
+    }
+
+    #[test]
+    fn test_set_authority_rejects_default_key() {
+        let authority = Pubkey::new_unique();
+        let mut counter = Counter {
+            count: 0,
+            authority,
+            ..Default::default()
+        };
+
+        let result = counter.transfer_authority(&authority, Pubkey::default());
+
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidAuthority.into());
+        assert_eq!(counter.authority, authority);
+    }
+
+    #[test]
+    fn test_pda_derivation() {
+        let user = Pubkey::new_unique();
+        let (pda, bump) = Counter::pda(&user);
+
+        // The same user always maps to the same counter, so a second
+        // initialize_pda targets an already-initialized account and fails
+        assert_eq!(Counter::pda(&user), (pda, bump));
+        assert_ne!(Counter::pda(&Pubkey::new_unique()).0, pda);
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[COUNTER_SEED, user.as_ref(), &[bump]],
+                &counter_program::ID
+            )
+            .unwrap(),
+            pda
+        );
+
+        let counter = Counter {
+            count: 0,
+            authority: user,
+            bump,
+            ..Default::default()
+        };
+        assert_eq!(counter.bump, bump);
+    }
+
+    #[test]
+    fn test_pda_counter_address_checked_with_stored_bump() {
+        let user = Pubkey::new_unique();
+        let (pda, bump) = Counter::pda(&user);
+        let mut counter = Counter {
+            authority: user,
+            bump,
+            creator: user,
+            ..Default::default()
+        };
+        assert!(counter.is_pda());
+        assert!(counter.has_address(&pda));
+        assert!(!counter.has_address(&Pubkey::new_unique()));
+        assert!(!counter.has_address(&Counter::pda(&Pubkey::new_unique()).0));
+
+        // The address stays valid after the authority moves on
+        let new_authority = Pubkey::new_unique();
+        counter.transfer_authority(&user, new_authority).unwrap();
+        assert!(counter.has_address(&pda));
+
+        // Keypair counters are not tied to an address
+        let keypair = Counter::default();
+        assert!(!keypair.is_pda());
+        assert!(keypair.has_address(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_counter_changed_event() {
+        let key = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut counter = Counter {
+            count: 41,
+            authority,
+            ..Default::default()
+        };
+
+        // Simulate increment
+        let old = counter.count;
+        counter.count = counter.count.checked_add(1).unwrap();
+        let event = counter.changed(key, old);
+
+        assert_eq!(event.counter, key);
+        assert_eq!(event.authority, authority);
+        assert_eq!(event.old, 41);
+        assert_eq!(event.new, 42);
+
+        // Round-trip through the logged encoding (discriminator + borsh)
+        let data = event.data();
+        assert_eq!(&data[..8], &CounterChanged::discriminator());
+        let decoded = CounterChanged::try_from_slice(&data[8..]).unwrap();
+        assert_eq!(decoded.new, 42);
+    }
+
+    #[test]
+    fn test_default_bounds() {
+        let counter = Counter::default();
+
+        assert_eq!(counter.min_bound, 0);
+        assert_eq!(counter.max_bound, u64::MAX);
+    }
+
+    #[test]
+    fn test_increment_to_max_bound() {
+        let mut counter = Counter {
+            count: 9,
+            min_bound: 5,
+            max_bound: 10,
+            ..Default::default()
+        };
+
+        // Simulate increment
+        counter.count = counter.count.checked_add(1).unwrap();
+
+        assert!(counter.check_bounds().is_ok());
+        assert_eq!(counter.count, 10);
+    }
+
+    #[test]
+    fn test_increment_past_max_bound() {
+        let mut counter = Counter {
+            count: 10,
+            min_bound: 5,
+            max_bound: 10,
+            ..Default::default()
+        };
+
+        // Simulate increment_by
+        counter.count = counter.count.checked_add(3).unwrap();
+
+        assert_eq!(
+            counter.check_bounds().unwrap_err(),
+            ErrorCode::OutOfBounds.into()
+        );
+    }
+
+    #[test]
+    fn test_set_below_min_bound() {
+        let mut counter = Counter {
+            count: 7,
+            min_bound: 5,
+            max_bound: 10,
+            ..Default::default()
+        };
+
+        counter.count = 5;
+        assert!(counter.check_bounds().is_ok());
+
+        counter.count = 4;
+        assert_eq!(
+            counter.check_bounds().unwrap_err(),
+            ErrorCode::OutOfBounds.into()
+        );
+    }
+
+    #[test]
+    fn test_mutations_fail_while_paused() {
+        let mut counter = Counter {
+            count: 3,
+            paused: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            counter.check_not_paused().unwrap_err(),
+            ErrorCode::Paused.into()
+        );
+
+        // Simulate unpause, then increment
+        counter.paused = false;
+        assert!(counter.check_not_paused().is_ok());
+        counter.count = counter.count.checked_add(1).unwrap();
+
+        assert_eq!(counter.count, 4);
+    }
+
+    #[test]
+    fn test_last_updated_advances() {
+        let mut counter = Counter::default();
+        let mut clock = Clock {
+            slot: 100,
+            unix_timestamp: 1_700_000_000,
+            ..Default::default()
+        };
+
+        // Simulate increment at the first clock
+        counter.count = counter.count.checked_add(1).unwrap();
+        counter.touch(&clock);
+        assert_eq!(counter.last_updated_slot, 100);
+        assert_eq!(counter.last_updated_ts, 1_700_000_000);
+
+        // Simulate a later increment
+        clock.slot = 150;
+        clock.unix_timestamp += 20;
+        counter.count = counter.count.checked_add(1).unwrap();
+        counter.touch(&clock);
+        assert_eq!(counter.last_updated_slot, 150);
+        assert_eq!(counter.last_updated_ts, 1_700_000_020);
+    }
+
+    /// Syscall stubs that keep return data in memory, as the runtime would
+    struct ReturnDataStubs(Mutex<Vec<u8>>);
+
+    impl SyscallStubs for ReturnDataStubs {
+        fn sol_set_return_data(&self, data: &[u8]) {
+            *self.0.lock().unwrap() = data.to_vec();
+        }
+
+        fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+            Some((counter_program::ID, self.0.lock().unwrap().clone()))
+        }
+    }
+
+    #[test]
+    fn test_return_data() {
+        program_stubs::set_syscall_stubs(Box::new(ReturnDataStubs(Mutex::new(Vec::new()))));
+        let mut counter = Counter {
+            count: 41,
+            ..Default::default()
+        };
+
+        // Simulate increment
+        counter.count = counter.count.checked_add(1).unwrap();
+        counter.publish_count();
+
+        let (program_id, data) = get_return_data().unwrap();
+        assert_eq!(program_id, counter_program::ID);
+        assert_eq!(u64::from_le_bytes(data.try_into().unwrap()), 42);
+    }
+
+    #[test]
+    fn test_step_increment() {
+        let mut counter = Counter::default();
+        assert_eq!(counter.step, 1);
+
+        counter.update_step(5).unwrap();
+
+        // Simulate increment
+        counter.count = counter.count.checked_add(counter.step).unwrap();
+        assert_eq!(counter.count, 5);
+    }
+
+    #[test]
+    fn test_zero_step_rejected() {
+        let mut counter = Counter::default();
+
+        assert_eq!(
+            counter.update_step(0).unwrap_err(),
+            ErrorCode::InvalidStep.into()
+        );
+        assert_eq!(counter.step, 1);
+    }
+
+    #[test]
+    fn test_propose_and_accept_authority() {
+        let authority = Pubkey::new_unique();
+        let new_authority = Pubkey::new_unique();
+        let mut counter = Counter {
+            authority,
+            ..Default::default()
+        };
+
+        counter
+            .propose_authority(&authority, new_authority)
+            .unwrap();
+        assert_eq!(counter.pending_authority, new_authority);
+        assert_eq!(counter.authority, authority);
+
+        counter.accept_authority(&new_authority).unwrap();
+        assert_eq!(counter.authority, new_authority);
+        assert_eq!(counter.pending_authority, Pubkey::default());
+    }
+
+    #[test]
+    fn test_accept_authority_wrong_signer() {
+        let authority = Pubkey::new_unique();
+        let mut counter = Counter {
+            authority,
+            ..Default::default()
+        };
+
+        // Nothing pending yet
+        assert_eq!(
+            counter.accept_authority(&authority).unwrap_err(),
+            ErrorCode::Unauthorized.into()
+        );
+
+        counter
+            .propose_authority(&authority, Pubkey::new_unique())
+            .unwrap();
+        assert_eq!(
+            counter.accept_authority(&Pubkey::new_unique()).unwrap_err(),
+            ErrorCode::Unauthorized.into()
+        );
+        assert_eq!(counter.authority, authority);
+    }
+
+    #[test]
+    fn test_propose_authority_unauthorized() {
+        let mut counter = Counter {
+            authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+
+        let result = counter.propose_authority(&Pubkey::new_unique(), Pubkey::new_unique());
+
+        assert_eq!(result.unwrap_err(), ErrorCode::Unauthorized.into());
+        assert_eq!(counter.pending_authority, Pubkey::default());
+    }
+
+    #[test]
+    fn test_history_ring() {
+        let mut counter = Counter::default();
+
+        // Simulate ten increments
+        for _ in 0..10 {
+            counter.count = counter.count.checked_add(1).unwrap();
+            counter.record_history();
+        }
+
+        assert_eq!(counter.recent_history(), [3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(counter.history_head, 2);
+    }
+
+    #[test]
+    fn test_history_partially_filled() {
+        let mut counter = Counter {
+            count: 50,
+            ..Default::default()
+        };
+
+        // Simulate set followed by two decrements
+        counter.record_history();
+        for _ in 0..2 {
+            counter.count = counter.count.checked_sub(1).unwrap();
+            counter.record_history();
+        }
+
+        assert_eq!(counter.recent_history(), [0, 0, 0, 0, 0, 50, 49, 48]);
+    }
+
+    #[test]
+    fn test_delegate_can_reset_but_not_transfer() {
+        let authority = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let mut counter = Counter {
+            count: 12,
+            authority,
+            delegate,
+            ..Default::default()
+        };
+
+        assert!(counter.check_authority_or_delegate(&delegate).is_ok());
+        assert!(counter.check_authority_or_delegate(&authority).is_ok());
+        assert_eq!(
+            counter
+                .check_authority_or_delegate(&Pubkey::new_unique())
+                .unwrap_err(),
+            ErrorCode::Unauthorized.into()
+        );
+
+        // Delegate cannot change authority
+        let result = counter.transfer_authority(&delegate, delegate);
+        assert_eq!(result.unwrap_err(), ErrorCode::Unauthorized.into());
+        assert_eq!(counter.authority, authority);
+    }
+
+    #[test]
+    fn test_set_name_grows_account() {
+        let mut data = vec![0u8; 8 + Counter::INIT_SPACE];
+        assert_eq!(Counter::read_name(&data), None);
+
+        // Simulate the realloc performed by the SetName context
+        data.resize(8 + Counter::INIT_SPACE + 4 + MAX_NAME_LEN, 0);
+        assert_eq!(data.len(), Counter::NAMED_SPACE);
+
+        Counter::write_name(&mut data, "Visitors").unwrap();
+        assert_eq!(Counter::read_name(&data), Some("Visitors".to_string()));
+
+        let longest = "n".repeat(MAX_NAME_LEN);
+        Counter::write_name(&mut data, &longest).unwrap();
+        assert_eq!(Counter::read_name(&data), Some(longest));
+    }
+
+    #[test]
+    fn test_set_name_too_long() {
+        let mut data = vec![0u8; Counter::NAMED_SPACE];
+
+        let result = Counter::write_name(&mut data, &"n".repeat(MAX_NAME_LEN + 1));
+
+        assert_eq!(result.unwrap_err(), ErrorCode::NameTooLong.into());
+        assert_eq!(Counter::read_name(&data), Some(String::new()));
+    }
+
+    #[test]
+    fn test_freeze_blocks_public_changes_but_not_set() {
+        let authority = Pubkey::new_unique();
+        let mut counter = Counter {
+            count: 5,
+            authority,
+            frozen: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            counter.check_not_frozen().unwrap_err(),
+            ErrorCode::Frozen.into()
+        );
+
+        // The authority can still set the value while frozen
+        assert!(counter.check_authority(&authority).is_ok());
+        assert!(counter.check_not_paused().is_ok());
+        counter.count = counter.state().set(9).unwrap();
+        assert_eq!(counter.count, 9);
+    }
+
+    #[test]
+    fn test_thaw_restores_increments() {
+        let mut counter = Counter {
+            count: 5,
+            frozen: true,
+            ..Default::default()
+        };
+
+        // Simulate thaw, then increment
+        counter.frozen = false;
+        assert!(counter.check_not_frozen().is_ok());
+        counter.count = counter.state().increment_by(counter.step).unwrap();
+
+        assert_eq!(counter.count, 6);
+    }
+
+    #[test]
+    fn test_initialize_twice_preserves_state() {
+        let authority = Pubkey::new_unique();
+        // A freshly created account is all zeroes
+        let mut counter = Counter {
+            step: 0,
+            max_bound: 0,
+            ..Default::default()
+        };
+        assert!(!counter.is_initialized());
+
+        assert!(counter.init_once(authority));
+        assert_eq!(counter.authority, authority);
+        assert_eq!(counter.step, 1);
+        counter.count = 42;
+
+        // A racing second initialize leaves the counter untouched
+        assert!(!counter.init_once(Pubkey::new_unique()));
+        assert_eq!(counter.count, 42);
+        assert_eq!(counter.authority, authority);
+    }
+
+    #[test]
+    fn test_initialize_after_set_name_is_noop() {
+        let key = Pubkey::new_unique();
+        let program_id = counter_program::ID;
+        let counter = Counter {
+            count: 42,
+            authority: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        counter.try_serialize(&mut data).unwrap();
+
+        // Simulate set_name growing the account past its initial size
+        data.resize(Counter::NAMED_SPACE, 0);
+        Counter::write_name(&mut data, "Visitors").unwrap();
+        let before = data.clone();
+
+        let mut lamports = 1_000_000;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &program_id,
+            false,
+            0,
+        );
+        assert!(Counter::exists(&info).unwrap());
+        drop(info);
+        assert_eq!(data, before);
+    }
+
+    #[test]
+    fn test_exists_only_for_program_owned_counters() {
+        let key = Pubkey::new_unique();
+        let system_id = System::id();
+        let mut lamports = 0;
+        let mut data = vec![];
+        let fresh = AccountInfo::new(
+            &key,
+            true,
+            true,
+            &mut lamports,
+            &mut data,
+            &system_id,
+            false,
+            0,
+        );
+        assert!(!Counter::exists(&fresh).unwrap());
+
+        let other_program = Pubkey::new_unique();
+        let mut lamports = 1_000_000;
+        let mut data = vec![0u8; 8 + Counter::INIT_SPACE];
+        let foreign = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &other_program,
+            false,
+            0,
+        );
+        assert!(Counter::exists(&foreign).is_err());
+    }
+
+    #[test]
+    fn test_set_respects_bounds() {
+        let counter = Counter {
+            count: 10,
+            min_bound: 5,
+            max_bound: 20,
+            ..Default::default()
+        };
+
+        let err = ErrorCode::from(counter.state().set(21).unwrap_err());
+        assert!(matches!(err, ErrorCode::OutOfBounds));
+        assert_eq!(counter.state().set(20), Ok(20));
+    }
+
+    #[test]
+    fn test_set_checked_compare_and_swap() {
+        let mut counter = Counter {
+            count: 7,
+            ..Default::default()
+        };
+
+        // Simulate set_checked with a fresh read
+        assert!(counter.check_current(7).is_ok());
+        counter.count = counter.state().set(11).unwrap();
+        assert_eq!(counter.count, 11);
+
+        // A second writer still holding the old read is rejected
+        assert_eq!(
+            counter.check_current(7).unwrap_err(),
+            ErrorCode::StaleValue.into()
+        );
+        assert_eq!(counter.count, 11);
+    }
+
+    #[test]
+    fn test_version_matches_crate() {
+        assert_eq!(VERSION, env!("CARGO_PKG_VERSION"));
+        assert_eq!(VERSION.split('.').count(), 3);
+    }
+
+    #[test]
+    fn test_decrement_to_floor() {
+        let mut counter = Counter {
+            count: 12,
+            floor: 10,
+            ..Default::default()
+        };
+
+        // Simulate decrement_by(2), landing exactly on the floor
+        let count = counter.state().decrement_by(2).unwrap();
+        assert!(counter.check_floor(count).is_ok());
+        counter.count = count;
+        assert_eq!(counter.count, 10);
+    }
+
+    #[test]
+    fn test_decrement_below_floor_rejected() {
+        let counter = Counter {
+            count: 10,
+            floor: 10,
+            ..Default::default()
+        };
+
+        let count = counter.state().decrement_by(counter.step).unwrap();
+        assert_eq!(
+            counter.check_floor(count).unwrap_err(),
+            ErrorCode::BelowFloor.into()
+        );
+        assert_eq!(counter.count, 10);
+    }
+
+    #[test]
+    fn test_leaderboard_evicts_lowest_and_stays_sorted() {
+        let mut leaderboard = Leaderboard::default();
+        let users: Vec<Pubkey> = (0..LEADERBOARD_LEN).map(|_| Pubkey::new_unique()).collect();
+        for (i, user) in users.iter().enumerate() {
+            assert!(leaderboard.record(*user, (i as u64 + 1) * 10));
+        }
+        assert_eq!(leaderboard.entries[0].count, 100);
+        assert_eq!(leaderboard.entries[LEADERBOARD_LEN - 1].user, users[0]);
+
+        // Not strictly higher than the lowest entry: rejected
+        assert!(!leaderboard.record(Pubkey::new_unique(), 10));
+
+        // A higher count evicts the lowest and lands in sorted position
+        let newcomer = Pubkey::new_unique();
+        assert!(leaderboard.record(newcomer, 55));
+        assert!(leaderboard.entries.iter().all(|entry| entry.user != users[0]));
+        assert_eq!(leaderboard.entries[5].user, newcomer);
+        assert!(leaderboard
+            .entries
+            .windows(2)
+            .all(|pair| pair[0].count >= pair[1].count));
+    }
+
+    #[test]
+    fn test_leaderboard_updates_existing_user() {
+        let mut leaderboard = Leaderboard::default();
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        assert!(leaderboard.record(alice, 5));
+        assert!(leaderboard.record(bob, 8));
+
+        // Alice overtakes Bob without gaining a second entry
+        assert!(leaderboard.record(alice, 9));
+        assert_eq!(leaderboard.entries[0].user, alice);
+        assert_eq!(leaderboard.entries[0].count, 9);
+        assert_eq!(leaderboard.entries[1].user, bob);
+        assert_eq!(leaderboard.entries[2].user, Pubkey::default());
+    }
+
+    #[test]
+    fn test_leaderboard_ranks_counters_by_creator() {
+        let mut leaderboard = Leaderboard::default();
+        let user = Pubkey::new_unique();
+        let (_, bump) = Counter::pda(&user);
+        let mut counter = Counter {
+            count: 4,
+            authority: user,
+            bump,
+            creator: user,
+            total_increments: 4,
+            ..Default::default()
+        };
+        assert!(leaderboard.record_counter(&counter));
+
+        // A new authority keeps updating the same entry
+        let new_authority = Pubkey::new_unique();
+        counter.transfer_authority(&user, new_authority).unwrap();
+        counter.add_increments(3);
+        assert!(leaderboard.record_counter(&counter));
+        assert_eq!(leaderboard.entries[0].user, user);
+        assert_eq!(leaderboard.entries[0].count, 7);
+        assert_eq!(leaderboard.entries[1].user, Pubkey::default());
+
+        // Keypair counters are never ranked
+        let keypair = Counter {
+            count: 100,
+            authority: Pubkey::new_unique(),
+            total_increments: 100,
+            ..Default::default()
+        };
+        assert!(!leaderboard.record_counter(&keypair));
+        assert_eq!(leaderboard.entries[0].user, user);
+    }
+
+    #[test]
+    fn test_leaderboard_ignores_set_count() {
+        let mut leaderboard = Leaderboard::default();
+        let alice = Pubkey::new_unique();
+        let bob = Pubkey::new_unique();
+        let mut counters = [alice, bob].map(|user| Counter {
+            authority: user,
+            bump: Counter::pda(&user).1,
+            creator: user,
+            ..Default::default()
+        });
+
+        // Alice increments twice, Bob once
+        for (counter, times) in counters.iter_mut().zip([2, 1]) {
+            for _ in 0..times {
+                counter.count = counter.state().increment_by(counter.step).unwrap();
+                counter.add_increments(counter.step);
+            }
+            assert!(leaderboard.record_counter(counter));
+        }
+
+        // Bob's authority sets a huge count, which is not an increment
+        let bob_counter = &mut counters[1];
+        bob_counter.count = bob_counter.state().set(u64::MAX).unwrap();
+        assert!(leaderboard.record_counter(bob_counter));
+
+        assert_eq!(leaderboard.entries[0].user, alice);
+        assert_eq!(leaderboard.entries[0].count, 2);
+        assert_eq!(leaderboard.entries[1].user, bob);
+        assert_eq!(leaderboard.entries[1].count, 1);
+    }
+
+    #[test]
+    fn test_renounce_authority() {
+        let authority = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let mut counter = Counter {
+            count: 5,
+            authority,
+            delegate,
+            ..Default::default()
+        };
+
+        assert!(counter.renounce_authority(&authority).is_ok());
+        assert!(counter.is_renounced());
+        assert!(counter.is_initialized());
+
+        // Renouncing again, and every authority-gated path, now fails
+        let unauthorized: anchor_lang::error::Error = ErrorCode::Unauthorized.into();
+        assert_eq!(
+            counter.renounce_authority(&authority).unwrap_err(),
+            unauthorized
+        );
+        assert_eq!(
+            counter.check_authority(&Pubkey::default()).unwrap_err(),
+            unauthorized
+        );
+        assert_eq!(
+            counter.check_authority_or_delegate(&delegate).unwrap_err(),
+            unauthorized
+        );
+        assert_eq!(
+            counter
+                .transfer_authority(&authority, Pubkey::new_unique())
+                .unwrap_err(),
+            unauthorized
+        );
+        assert_eq!(
+            counter
+                .propose_authority(&Pubkey::default(), Pubkey::new_unique())
+                .unwrap_err(),
+            unauthorized
+        );
+        assert_eq!(counter.authority, Pubkey::default());
+    }
 }